@@ -0,0 +1,95 @@
+use std::fmt;
+
+use crate::BinarySearchTree;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    EvictSmallest,
+    EvictLargest,
+}
+
+pub struct BoundedBst<T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    tree: BinarySearchTree<T>,
+    len: usize,
+    capacity: usize,
+    policy: EvictionPolicy,
+}
+
+impl<T> BoundedBst<T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    pub fn new(capacity: usize, policy: EvictionPolicy) -> Self {
+        BoundedBst { tree: BinarySearchTree::new(), len: 0, capacity, policy }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn as_tree(&self) -> &BinarySearchTree<T> {
+        &self.tree
+    }
+
+    pub fn insert(&mut self, value: T) -> Option<T> {
+        if self.tree.try_insert(value) == Ok(true) {
+            self.len += 1;
+        }
+
+        if self.len <= self.capacity {
+            return None;
+        }
+
+        self.len -= 1;
+
+        match self.policy {
+            EvictionPolicy::EvictSmallest => self.tree.pop_min(),
+            EvictionPolicy::EvictLargest => self.tree.pop_max(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_bst_evict_smallest_test() {
+        let mut bounded = BoundedBst::new(10, EvictionPolicy::EvictSmallest);
+
+        for value in 0..15 {
+            bounded.insert((value * 7) % 15);
+        }
+
+        assert_eq!(10, bounded.len());
+        assert_eq!((5..15).collect::<Vec<_>>(), bounded.as_tree().to_sorted_vec());
+        assert_eq!(Some(5), bounded.insert(15));
+        assert_eq!(None, bounded.insert(15));
+        assert!(bounded.as_tree().is_valid_bst());
+    }
+
+    #[test]
+    fn bounded_bst_evict_largest_test() {
+        let mut bounded = BoundedBst::new(10, EvictionPolicy::EvictLargest);
+
+        for value in 0..15 {
+            bounded.insert((value * 7) % 15);
+        }
+
+        assert_eq!(10, bounded.len());
+        assert_eq!((0..10).collect::<Vec<_>>(), bounded.as_tree().to_sorted_vec());
+        assert_eq!(Some(20), bounded.insert(20));
+        assert!(bounded.as_tree().is_valid_bst());
+    }
+}
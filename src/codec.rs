@@ -0,0 +1,171 @@
+use std::fmt;
+
+use crate::{BinarySearchTree, DecodeError};
+
+const EMPTY_TAG: u8 = 0;
+const NODE_TAG: u8 = 1;
+
+/// Deepest nesting `from_bytes` accepts, so that crafted input cannot overflow the stack.
+pub const MAX_DECODE_DEPTH: usize = 1_000;
+
+pub trait FixedBytes: Sized {
+    const SIZE: usize;
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>);
+
+    fn read_bytes(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_fixed_bytes {
+    ($($t:ty),*) => {
+        $(
+            impl FixedBytes for $t {
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                fn write_bytes(&self, bytes: &mut Vec<u8>) {
+                    bytes.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn read_bytes(bytes: &[u8]) -> Self {
+                    let mut buf = [0; std::mem::size_of::<$t>()];
+                    buf.copy_from_slice(bytes);
+                    <$t>::from_le_bytes(buf)
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_bytes!(i64, u64);
+
+impl<T> BinarySearchTree<T>
+where
+    T: fmt::Debug + PartialOrd + FixedBytes,
+{
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.recursive_to_bytes(&mut bytes, self);
+        bytes
+    }
+
+    fn recursive_to_bytes(&self, bytes: &mut Vec<u8>, root: &BinarySearchTree<T>) {
+        match root {
+            BinarySearchTree::Node { value, left, right } => {
+                bytes.push(NODE_TAG);
+                value.write_bytes(bytes);
+                self.recursive_to_bytes(bytes, left);
+                self.recursive_to_bytes(bytes, right);
+            }
+            BinarySearchTree::Empty => bytes.push(EMPTY_TAG),
+        }
+    }
+
+    /// Decodes the format written by [`to_bytes`](Self::to_bytes). Trees nested deeper than
+    /// [`MAX_DECODE_DEPTH`] are rejected with [`DecodeError::TooDeep`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut rest = bytes;
+        let tree = Self::recursive_from_bytes(&mut rest, 1)?;
+
+        if rest.is_empty() {
+            Ok(tree)
+        } else {
+            Err(DecodeError::TrailingBytes)
+        }
+    }
+
+    fn recursive_from_bytes(rest: &mut &[u8], depth: usize) -> Result<Self, DecodeError> {
+        let (&tag, tail) = rest.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+        *rest = tail;
+
+        match tag {
+            NODE_TAG => {
+                if depth > MAX_DECODE_DEPTH {
+                    return Err(DecodeError::TooDeep);
+                }
+
+                if rest.len() < T::SIZE {
+                    return Err(DecodeError::UnexpectedEnd);
+                }
+
+                let (value, tail) = rest.split_at(T::SIZE);
+                *rest = tail;
+
+                Ok(BinarySearchTree::Node {
+                    value: T::read_bytes(value),
+                    left: Box::new(Self::recursive_from_bytes(rest, depth + 1)?),
+                    right: Box::new(Self::recursive_from_bytes(rest, depth + 1)?),
+                })
+            }
+            EMPTY_TAG => Ok(BinarySearchTree::Empty),
+            tag => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bst_bytes_round_trip_test() {
+        let mut bst: BinarySearchTree<i64> = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(-4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+
+        let bytes = bst.to_bytes();
+        assert_eq!(8 * 9 + 9, bytes.len());
+        let decoded = BinarySearchTree::<i64>::from_bytes(&bytes).unwrap();
+        assert_eq!(bst.pre_order(), decoded.pre_order());
+        assert_eq!(bst.breadth_first(), decoded.breadth_first());
+        assert!(BinarySearchTree::<u64>::from_bytes(&[EMPTY_TAG]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn bst_from_bytes_error_test() {
+        assert_eq!(
+            Err(DecodeError::UnexpectedEnd),
+            BinarySearchTree::<u64>::from_bytes(&[]).map(|_| ())
+        );
+        assert_eq!(
+            Err(DecodeError::UnexpectedEnd),
+            BinarySearchTree::<u64>::from_bytes(&[NODE_TAG, 1, 2]).map(|_| ()),
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidTag(7)),
+            BinarySearchTree::<u64>::from_bytes(&[7]).map(|_| ())
+        );
+        assert_eq!(
+            Err(DecodeError::TrailingBytes),
+            BinarySearchTree::<u64>::from_bytes(&[EMPTY_TAG, EMPTY_TAG]).map(|_| ()),
+        );
+
+        let nested = |depth: usize| {
+            let mut bytes = Vec::new();
+
+            for value in 0..depth as u64 {
+                bytes.push(NODE_TAG);
+                value.write_bytes(&mut bytes);
+            }
+
+            bytes.resize(bytes.len() + depth + 1, EMPTY_TAG);
+            bytes
+        };
+
+        let decoded = BinarySearchTree::<u64>::from_bytes(&nested(MAX_DECODE_DEPTH)).unwrap();
+        assert_eq!(MAX_DECODE_DEPTH, decoded.len());
+        assert_eq!(
+            Err(DecodeError::TooDeep),
+            BinarySearchTree::<u64>::from_bytes(&nested(MAX_DECODE_DEPTH + 1)).map(|_| ()),
+        );
+        assert_eq!(
+            Err(DecodeError::TooDeep),
+            BinarySearchTree::<u64>::from_bytes(&vec![NODE_TAG; 2_000_000]).map(|_| ()),
+        );
+    }
+}
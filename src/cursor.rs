@@ -0,0 +1,107 @@
+use std::fmt;
+
+use crate::BinarySearchTree;
+
+enum Position<'a, T> {
+    BeforeFirst,
+    At(&'a T),
+    AfterLast,
+}
+
+pub struct Cursor<'a, T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    tree: &'a BinarySearchTree<T>,
+    position: Position<'a, T>,
+}
+
+impl<'a, T> Cursor<'a, T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    pub(crate) fn new(tree: &'a BinarySearchTree<T>, target: &T) -> Self {
+        let position = match tree.partition_point(|value| value >= target) {
+            Some(value) => Position::At(value),
+            None => Position::AfterLast,
+        };
+
+        Cursor { tree, position }
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        match self.position {
+            Position::At(value) => Some(value),
+            Position::BeforeFirst | Position::AfterLast => None,
+        }
+    }
+
+    pub fn prev(&mut self) -> Option<&'a T> {
+        let prev = match self.position {
+            Position::BeforeFirst => None,
+            Position::At(value) => self.tree.predecessor(value),
+            Position::AfterLast => self.tree.max(),
+        };
+
+        self.position = match prev {
+            Some(value) => Position::At(value),
+            None => Position::BeforeFirst,
+        };
+        prev
+    }
+}
+
+impl<'a, T> Iterator for Cursor<'a, T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = match self.position {
+            Position::BeforeFirst => self.tree.min(),
+            Position::At(value) => self.tree.successor(value),
+            Position::AfterLast => None,
+        };
+
+        self.position = match next {
+            Some(value) => Position::At(value),
+            None => Position::AfterLast,
+        };
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_next_prev_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+
+        let mut cursor = bst.cursor_at(&37);
+        assert_eq!(Some(&37), cursor.current());
+        assert_eq!(Some(&60), cursor.next());
+        assert_eq!(Some(&84), cursor.next());
+        assert_eq!(Some(&60), cursor.prev());
+        assert_eq!(Some(&37), cursor.prev());
+
+        let mut cursor = bst.cursor_at(&50);
+        assert_eq!(Some(&60), cursor.current());
+        assert_eq!(Some(&37), cursor.prev());
+
+        let mut cursor = bst.cursor_at(&101);
+        assert_eq!(None, cursor.current());
+        assert_eq!(None, cursor.next());
+        assert_eq!(Some(&100), cursor.prev());
+    }
+}
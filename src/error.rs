@@ -0,0 +1,77 @@
+use std::{error::Error, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepthExceeded {
+    pub depth: usize,
+}
+
+impl fmt::Display for DepthExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "traversal exceeded the maximum depth at depth {}", self.depth)
+    }
+}
+
+impl Error for DepthExceeded {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    UnexpectedEnd,
+    InvalidTag(u8),
+    TrailingBytes,
+    TooDeep,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            DecodeError::InvalidTag(tag) => write!(f, "invalid node tag {:#04x}", tag),
+            DecodeError::TrailingBytes => write!(f, "trailing bytes after the encoded tree"),
+            DecodeError::TooDeep => write!(f, "encoded tree is nested too deeply"),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncomparableError;
+
+impl fmt::Display for IncomparableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value cannot be compared with the values already in the tree")
+    }
+}
+
+impl Error for IncomparableError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedEnd,
+    UnexpectedChar { position: usize, found: char },
+    MissingLabel { position: usize },
+    InvalidLabel { position: usize, label: String },
+    TooDeep { position: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ParseError::UnexpectedChar { position, found } => {
+                write!(f, "unexpected character {:?} at position {}", found, position)
+            }
+            ParseError::MissingLabel { position } => {
+                write!(f, "missing node label at position {}", position)
+            }
+            ParseError::InvalidLabel { position, label } => {
+                write!(f, "invalid node label {:?} at position {}", label, position)
+            }
+            ParseError::TooDeep { position } => {
+                write!(f, "parentheses nested too deeply at position {}", position)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,205 @@
+use std::fmt;
+
+use crate::BinarySearchTree;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DfsOrder {
+    PreOrder,
+    InOrder,
+    PostOrder,
+}
+
+pub struct DfsIter<'a, T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    order: DfsOrder,
+    stack: Vec<(&'a BinarySearchTree<T>, bool)>,
+}
+
+impl<'a, T> DfsIter<'a, T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    pub(crate) fn new(root: &'a BinarySearchTree<T>, order: DfsOrder) -> Self {
+        DfsIter { order, stack: vec![(root, false)] }
+    }
+}
+
+impl<'a, T> Iterator for DfsIter<'a, T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, expanded)) = self.stack.pop() {
+            if let BinarySearchTree::Node { value, left, right } = node {
+                if expanded {
+                    return Some(value);
+                }
+
+                match self.order {
+                    DfsOrder::PreOrder => {
+                        self.stack.extend([(&**right, false), (&**left, false), (node, true)])
+                    }
+                    DfsOrder::InOrder => {
+                        self.stack.extend([(&**right, false), (node, true), (&**left, false)])
+                    }
+                    DfsOrder::PostOrder => {
+                        self.stack.extend([(node, true), (&**right, false), (&**left, false)])
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+pub struct Iter<'a, T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    front: Vec<(&'a T, &'a BinarySearchTree<T>)>,
+    back: Vec<(&'a T, &'a BinarySearchTree<T>)>,
+    remaining: usize,
+}
+
+impl<'a, T> Iter<'a, T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    pub(crate) fn new(root: &'a BinarySearchTree<T>) -> Self {
+        let mut iter = Iter { front: Vec::new(), back: Vec::new(), remaining: root.len() };
+        iter.push_left_spine(root);
+        iter.push_right_spine(root);
+        iter
+    }
+
+    fn push_left_spine(&mut self, mut node: &'a BinarySearchTree<T>) {
+        while let BinarySearchTree::Node { value, left, right } = node {
+            self.front.push((value, right));
+            node = left;
+        }
+    }
+
+    fn push_right_spine(&mut self, mut node: &'a BinarySearchTree<T>) {
+        while let BinarySearchTree::Node { value, left, right } = node {
+            self.back.push((value, left));
+            node = right;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let (value, right) = self.front.pop()?;
+        self.push_left_spine(right);
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let (value, left) = self.back.pop()?;
+        self.push_right_spine(left);
+        self.remaining -= 1;
+        Some(value)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> where T: fmt::Debug + PartialOrd {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dfs_iter_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(bst.pre_order(), bst.dfs(DfsOrder::PreOrder).collect::<Vec<_>>());
+        assert_eq!(bst.values(), bst.dfs(DfsOrder::InOrder).collect::<Vec<_>>());
+        assert_eq!(bst.post_order(), bst.dfs(DfsOrder::PostOrder).collect::<Vec<_>>());
+        assert_eq!(vec![&1, &4], bst.dfs(DfsOrder::InOrder).take(2).collect::<Vec<_>>());
+        assert_eq!(None, BinarySearchTree::<i32>::new().dfs(DfsOrder::PreOrder).next());
+    }
+
+    #[test]
+    fn iter_double_ended_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(bst.values(), bst.iter().collect::<Vec<_>>());
+        assert_eq!(
+            vec![&100, &90, &84, &60, &37, &12, &4, &1],
+            bst.iter().rev().collect::<Vec<_>>()
+        );
+
+        let mut iter = bst.iter();
+        assert_eq!(Some(&1), iter.next());
+        assert_eq!(Some(&100), iter.next_back());
+        assert_eq!(Some(&4), iter.next());
+        assert_eq!(Some(&90), iter.next_back());
+        assert_eq!(Some(&84), iter.next_back());
+        assert_eq!(Some(&12), iter.next());
+        assert_eq!(Some(&37), iter.next());
+        assert_eq!(Some(&60), iter.next_back());
+        assert_eq!(None, iter.next());
+        assert_eq!(None, iter.next_back());
+    }
+
+    #[test]
+    fn iter_exact_size_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(0, bst.iter().len());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+
+        let mut iter = bst.iter();
+        assert_eq!(4, iter.len());
+        iter.next();
+        assert_eq!(3, iter.len());
+        iter.next_back();
+        assert_eq!(2, iter.len());
+        iter.by_ref().for_each(drop);
+        assert_eq!(0, iter.len());
+    }
+}
@@ -1,4 +1,43 @@
-use std::{cmp::Ordering, collections::VecDeque, fmt};
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::DefaultHasher, HashMap, VecDeque},
+    fmt,
+    hash::{Hash, Hasher},
+    mem,
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+mod bounded;
+mod codec;
+mod cursor;
+mod error;
+mod iter;
+mod map;
+mod ordered;
+#[cfg(feature = "rayon")]
+mod par;
+mod profiled;
+mod rng;
+mod running_median;
+mod small;
+
+use rng::SplitMix64;
+
+pub use bounded::{BoundedBst, EvictionPolicy};
+pub use codec::{FixedBytes, MAX_DECODE_DEPTH};
+pub use cursor::Cursor;
+pub use error::{DecodeError, DepthExceeded, IncomparableError, ParseError};
+pub use iter::{DfsIter, DfsOrder, Iter};
+pub use map::{BinarySearchMap, Entry, OccupiedEntry, VacantEntry};
+pub use ordered::{OrderedBst, SortOrder};
+pub use profiled::ProfiledBst;
+pub use running_median::RunningMedian;
+pub use small::SmallValues;
+
+/// Deepest parenthesis nesting `from_newick` accepts, so that crafted input cannot overflow the
+/// stack.
+pub const MAX_NEWICK_DEPTH: usize = 1_000;
 
 pub enum BinarySearchTree<T>
 where
@@ -8,6 +47,31 @@ where
     Empty,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MedianResult<T> {
+    One(T),
+    Two(T, T),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalancePolicy {
+    MinHeight,
+    Random(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Skew {
+    LeftHeavy,
+    RightHeavy,
+    Balanced,
+}
+
 impl<T> Default for BinarySearchTree<T>
 where
     T: fmt::Debug + PartialOrd,
@@ -25,6 +89,17 @@ where
         BinarySearchTree::Empty
     }
 
+    /// Creates an empty [`OrderedBst`] that keeps its values from largest to smallest.
+    pub fn new_descending() -> OrderedBst<T> {
+        OrderedBst::new(SortOrder::Descending)
+    }
+
+    /// Builds a node from its parts without checking the BST ordering, so it can also produce
+    /// invalid trees.
+    pub fn node(value: T, left: BinarySearchTree<T>, right: BinarySearchTree<T>) -> Self {
+        BinarySearchTree::Node { value, left: Box::new(left), right: Box::new(right) }
+    }
+
     pub fn insert(&mut self, new_value: T) {
         match self {
             BinarySearchTree::Node { ref value, ref mut left, ref mut right } => {
@@ -44,6 +119,85 @@ where
         }
     }
 
+    pub fn remove(&mut self, target: &T) -> bool {
+        self.take(target).is_some()
+    }
+
+    pub fn take(&mut self, target: &T) -> Option<T> {
+        match self {
+            BinarySearchTree::Node { value, left, right } => match target.partial_cmp(value) {
+                Some(Ordering::Less) => left.take(target),
+                Some(Ordering::Greater) => right.take(target),
+                Some(Ordering::Equal) => self.remove_root(),
+                None => None,
+            },
+            BinarySearchTree::Empty => None,
+        }
+    }
+
+    fn remove_root(&mut self) -> Option<T> {
+        match mem::take(self) {
+            BinarySearchTree::Node { value, left, mut right } => {
+                *self = match *left {
+                    BinarySearchTree::Empty => *right,
+                    left => match right.pop_min() {
+                        Some(successor) => {
+                            BinarySearchTree::Node { value: successor, left: Box::new(left), right }
+                        }
+                        None => left,
+                    },
+                };
+                Some(value)
+            }
+            BinarySearchTree::Empty => None,
+        }
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        match self {
+            BinarySearchTree::Node { left, .. }
+                if matches!(**left, BinarySearchTree::Node { .. }) =>
+            {
+                left.pop_min()
+            }
+            _ => self.remove_root(),
+        }
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        match self {
+            BinarySearchTree::Node { right, .. }
+                if matches!(**right, BinarySearchTree::Node { .. }) =>
+            {
+                right.pop_max()
+            }
+            _ => self.remove_root(),
+        }
+    }
+
+    /// The boxed representation frees each node as soon as it is removed, so there is no dead
+    /// storage to compact and this is a no-op that runs in constant time.
+    pub fn shrink_to_fit(&mut self) {}
+
+    pub fn get_by<F, K>(&self, key: &K, extract: F) -> Option<&T>
+    where
+        F: Fn(&T) -> &K,
+        K: PartialOrd,
+    {
+        let mut node = self;
+
+        while let BinarySearchTree::Node { value, left, right } = node {
+            match key.partial_cmp(extract(value)) {
+                Some(Ordering::Less) => node = left,
+                Some(Ordering::Greater) => node = right,
+                Some(Ordering::Equal) => return Some(value),
+                None => return None,
+            }
+        }
+
+        None
+    }
+
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
@@ -67,13 +221,15 @@ where
         }
     }
 
+    pub fn pre_order(&self) -> Vec<&T> {
+        let mut v = Vec::new();
+        self.recursive_pre_order_traversal(&mut v, self);
+        v
+    }
+
     pub fn pre_order_traversal(&self) -> Option<Vec<&T>> {
         match self {
-            root @ BinarySearchTree::Node { .. } => {
-                let mut v = Vec::new();
-                self.recursive_pre_order_traversal(&mut v, root);
-                Some(v)
-            }
+            BinarySearchTree::Node { .. } => Some(self.pre_order()),
             BinarySearchTree::Empty => None,
         }
     }
@@ -88,11 +244,7 @@ where
 
     pub fn in_order_traversal(&self) -> Option<Vec<&T>> {
         match self {
-            root @ BinarySearchTree::Node { .. } => {
-                let mut v = Vec::new();
-                self.recursive_in_order_traversal(&mut v, root);
-                Some(v)
-            }
+            BinarySearchTree::Node { .. } => Some(self.values()),
             BinarySearchTree::Empty => None,
         }
     }
@@ -105,13 +257,15 @@ where
         }
     }
 
+    pub fn post_order(&self) -> Vec<&T> {
+        let mut v = Vec::new();
+        self.recursive_post_order_traversal(&mut v, self);
+        v
+    }
+
     pub fn post_order_traversal(&self) -> Option<Vec<&T>> {
         match self {
-            root @ BinarySearchTree::Node { .. } => {
-                let mut v = Vec::new();
-                self.recursive_post_order_traversal(&mut v, root);
-                Some(v)
-            }
+            BinarySearchTree::Node { .. } => Some(self.post_order()),
             BinarySearchTree::Empty => None,
         }
     }
@@ -129,6 +283,10 @@ where
     }
 
     pub fn breadth_first_traversal(&self) -> Option<Vec<&T>> {
+        Some(self.breadth_first())
+    }
+
+    pub fn breadth_first(&self) -> Vec<&T> {
         let mut v = Vec::new();
         let mut queue = VecDeque::new();
         queue.push_back(self);
@@ -147,70 +305,3954 @@ where
             }
         }
 
-        Some(v)
+        v
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub fn kth_smallest(&self, k: usize) -> Option<&T> {
+        let mut remaining = k;
+        self.recursive_kth_smallest(&mut remaining, self)
+    }
 
-    #[test]
-    fn bst_pre_order_traversal_test() {
-        let mut bst = BinarySearchTree::new();
-        bst.insert(60);
-        bst.insert(12);
-        bst.insert(90);
-        bst.insert(4);
-        bst.insert(1);
-        bst.insert(100);
-        bst.insert(37);
-        bst.insert(84);
-        assert_eq!(Some(vec![&60, &12, &4, &1, &37, &90, &84, &100]), bst.pre_order_traversal(),);
+    fn recursive_kth_smallest<'a>(
+        &self,
+        remaining: &mut usize,
+        root: &'a BinarySearchTree<T>,
+    ) -> Option<&'a T> {
+        match root {
+            BinarySearchTree::Node { value, left, right } => {
+                if let Some(found) = self.recursive_kth_smallest(remaining, left) {
+                    return Some(found);
+                }
+
+                if *remaining == 0 {
+                    return Some(value);
+                }
+
+                *remaining -= 1;
+                self.recursive_kth_smallest(remaining, right)
+            }
+            BinarySearchTree::Empty => None,
+        }
     }
 
-    #[test]
-    fn bst_in_order_traversal_test() {
-        let mut bst = BinarySearchTree::new();
-        bst.insert(60);
-        bst.insert(12);
-        bst.insert(90);
-        bst.insert(4);
-        bst.insert(1);
-        bst.insert(100);
-        bst.insert(37);
-        bst.insert(84);
-        assert_eq!(Some(vec![&1, &4, &12, &37, &60, &84, &90, &100]), bst.in_order_traversal(),);
+    /// Returns the value at the `p`-th percentile (`0.0..=1.0`) using the nearest-rank method, so
+    /// `percentile(0.5)` is the lower median of an even-sized tree.
+    pub fn percentile(&self, p: f64) -> Option<&T> {
+        if !(0.0..=1.0).contains(&p) {
+            return None;
+        }
+
+        let rank = (p * self.len() as f64).ceil() as usize;
+        self.kth_smallest(rank.saturating_sub(1))
     }
 
-    #[test]
-    fn bst_post_order_traversal_test() {
-        let mut bst = BinarySearchTree::new();
-        bst.insert(60);
-        bst.insert(12);
-        bst.insert(90);
-        bst.insert(4);
-        bst.insert(1);
-        bst.insert(100);
-        bst.insert(37);
-        bst.insert(84);
-        assert_eq!(Some(vec![&1, &4, &37, &12, &84, &100, &90, &60]), bst.post_order_traversal(),);
+    pub fn median(&self) -> Option<MedianResult<&T>> {
+        let len = self.len();
+
+        if len == 0 {
+            None
+        } else if len % 2 == 1 {
+            self.kth_smallest(len / 2).map(MedianResult::One)
+        } else {
+            Some(MedianResult::Two(self.kth_smallest(len / 2 - 1)?, self.kth_smallest(len / 2)?))
+        }
     }
 
-    #[test]
-    fn bst_breadth_first_traversal_test() {
-        let mut bst = BinarySearchTree::new();
-        bst.insert(60);
-        bst.insert(12);
-        bst.insert(90);
-        bst.insert(4);
-        bst.insert(1);
-        bst.insert(100);
-        bst.insert(37);
-        bst.insert(84);
-        assert_eq!(
-            Some(vec![&60, &12, &90, &4, &37, &84, &100, &1]),
-            bst.breadth_first_traversal(),
-        );
+    /// Returns the smallest value for which `pred` holds. `pred` must be monotone over the sorted
+    /// order (false for every value before the boundary, true for every value after it), otherwise
+    /// the result is unspecified.
+    pub fn partition_point<F: Fn(&T) -> bool>(&self, pred: F) -> Option<&T> {
+        let mut node = self;
+        let mut boundary = None;
+
+        while let BinarySearchTree::Node { value, left, right } = node {
+            if pred(value) {
+                boundary = Some(value);
+                node = left;
+            } else {
+                node = right;
+            }
+        }
+
+        boundary
+    }
+
+    pub fn values(&self) -> Vec<&T> {
+        let mut v = Vec::new();
+        self.recursive_in_order_traversal(&mut v, self);
+        v
+    }
+
+    pub fn is_full(&self) -> bool {
+        match self {
+            BinarySearchTree::Node { left, right, .. } => match (&**left, &**right) {
+                (BinarySearchTree::Empty, BinarySearchTree::Empty) => true,
+                (BinarySearchTree::Node { .. }, BinarySearchTree::Node { .. }) => {
+                    left.is_full() && right.is_full()
+                }
+                _ => false,
+            },
+            BinarySearchTree::Empty => true,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        let mut queue = VecDeque::new();
+        let mut seen_gap = false;
+        queue.push_back(self);
+
+        while let Some(node) = queue.pop_front() {
+            match node {
+                BinarySearchTree::Node { left, right, .. } => {
+                    if seen_gap {
+                        return false;
+                    }
+
+                    queue.push_back(left);
+                    queue.push_back(right);
+                }
+                BinarySearchTree::Empty => seen_gap = true,
+            }
+        }
+
+        true
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph BinarySearchTree {\n");
+        let mut next_id = 0;
+        self.recursive_to_dot(&mut dot, &mut next_id, self);
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn recursive_to_dot(&self, dot: &mut String, next_id: &mut usize, root: &BinarySearchTree<T>) {
+        if let BinarySearchTree::Node { value, left, right } = root {
+            let id = *next_id;
+            *next_id += 1;
+            let label = format!("{:?}", value).replace('\\', "\\\\").replace('"', "\\\"");
+            dot.push_str(&format!("    n{} [label=\"{}\"];\n", id, label));
+
+            let has_children = matches!(**left, BinarySearchTree::Node { .. })
+                || matches!(**right, BinarySearchTree::Node { .. });
+
+            for child in [left, right] {
+                match **child {
+                    BinarySearchTree::Node { .. } => {
+                        dot.push_str(&format!("    n{} -> n{};\n", id, *next_id));
+                        self.recursive_to_dot(dot, next_id, child);
+                    }
+                    BinarySearchTree::Empty if has_children => {
+                        dot.push_str(&format!("    n{} [shape=point];\n", *next_id));
+                        dot.push_str(&format!("    n{} -> n{};\n", id, *next_id));
+                        *next_id += 1;
+                    }
+                    BinarySearchTree::Empty => {}
+                }
+            }
+        }
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        let mut node = self;
+        let mut min = None;
+
+        while let BinarySearchTree::Node { value, left, .. } = node {
+            min = Some(value);
+            node = left;
+        }
+
+        min
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        let mut node = self;
+        let mut max = None;
+
+        while let BinarySearchTree::Node { value, right, .. } = node {
+            max = Some(value);
+            node = right;
+        }
+
+        max
+    }
+
+    pub fn successor(&self, target: &T) -> Option<&T> {
+        self.partition_point(|value| value > target)
+    }
+
+    pub fn predecessor(&self, target: &T) -> Option<&T> {
+        let mut node = self;
+        let mut predecessor = None;
+
+        while let BinarySearchTree::Node { value, left, right } = node {
+            if value < target {
+                predecessor = Some(value);
+                node = right;
+            } else {
+                node = left;
+            }
+        }
+
+        predecessor
+    }
+
+    pub fn cursor_at(&self, target: &T) -> Cursor<'_, T> {
+        Cursor::new(self, target)
+    }
+
+    pub fn pre_order_into<'a>(&'a self, buf: &mut Vec<&'a T>) {
+        buf.clear();
+        self.recursive_pre_order_traversal(buf, self);
+    }
+
+    pub fn in_order_into<'a>(&'a self, buf: &mut Vec<&'a T>) {
+        buf.clear();
+        self.recursive_in_order_traversal(buf, self);
+    }
+
+    pub fn post_order_into<'a>(&'a self, buf: &mut Vec<&'a T>) {
+        buf.clear();
+        self.recursive_post_order_traversal(buf, self);
+    }
+
+    pub fn try_in_order(&self, max_depth: usize) -> Result<Vec<&T>, DepthExceeded> {
+        let mut v = Vec::new();
+        self.recursive_try_in_order(&mut v, self, 1, max_depth)?;
+        Ok(v)
+    }
+
+    fn recursive_try_in_order<'a>(
+        &self,
+        v: &mut Vec<&'a T>,
+        root: &'a BinarySearchTree<T>,
+        depth: usize,
+        max_depth: usize,
+    ) -> Result<(), DepthExceeded> {
+        if let BinarySearchTree::Node { value, left, right } = root {
+            if depth > max_depth {
+                return Err(DepthExceeded { depth });
+            }
+
+            self.recursive_try_in_order(v, left, depth + 1, max_depth)?;
+            v.push(value);
+            self.recursive_try_in_order(v, right, depth + 1, max_depth)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut v = Vec::new();
+        Self::recursive_into_sorted_vec(&mut v, self);
+        v
+    }
+
+    fn recursive_into_sorted_vec(v: &mut Vec<T>, root: BinarySearchTree<T>) {
+        if let BinarySearchTree::Node { value, left, right } = root {
+            Self::recursive_into_sorted_vec(v, *left);
+            v.push(value);
+            Self::recursive_into_sorted_vec(v, *right);
+        }
+    }
+
+    fn build_balanced<I: Iterator<Item = T>>(values: &mut I, len: usize) -> Self {
+        if len == 0 {
+            return BinarySearchTree::Empty;
+        }
+
+        let left = Self::build_balanced(values, len / 2);
+
+        match values.next() {
+            Some(value) => BinarySearchTree::Node {
+                value,
+                left: Box::new(left),
+                right: Box::new(Self::build_balanced(values, len - len / 2 - 1)),
+            },
+            None => left,
+        }
+    }
+
+    pub fn split_off_at_rank(&mut self, k: usize) -> BinarySearchTree<T> {
+        let mut values = mem::take(self).into_sorted_vec().into_iter();
+        let k = k.min(values.len());
+        let rest = values.len() - k;
+        *self = Self::build_balanced(&mut values, k);
+        Self::build_balanced(&mut values, rest)
+    }
+
+    pub fn append(&mut self, other: &mut BinarySearchTree<T>) {
+        self.recursive_append(mem::take(other));
+    }
+
+    fn recursive_append(&mut self, root: BinarySearchTree<T>) {
+        if let BinarySearchTree::Node { value, left, right } = root {
+            self.insert(value);
+            self.recursive_append(*left);
+            self.recursive_append(*right);
+        }
+    }
+
+    pub fn to_sorted_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.values().into_iter().cloned().collect()
+    }
+
+    pub fn nearest_k(&self, center: &T, k: usize) -> Vec<&T>
+    where
+        T: Sub<Output = T> + PartialOrd + Copy,
+    {
+        let distance =
+            |value: &T| if *value > *center { *value - *center } else { *center - *value };
+        let mut below = self.predecessor(center);
+        let mut above = self.partition_point(|value| value >= center);
+        let mut nearest = Vec::new();
+
+        while nearest.len() < k {
+            match (below, above) {
+                (Some(low), Some(high)) if distance(low) <= distance(high) => {
+                    nearest.push(low);
+                    below = self.predecessor(low);
+                }
+                (_, Some(high)) => {
+                    nearest.push(high);
+                    above = self.successor(high);
+                }
+                (Some(low), None) => {
+                    nearest.push(low);
+                    below = self.predecessor(low);
+                }
+                (None, None) => break,
+            }
+        }
+
+        nearest
+    }
+
+    pub fn count_half_nodes(&self) -> usize {
+        match self {
+            BinarySearchTree::Node { left, right, .. } => {
+                let is_half = matches!(**left, BinarySearchTree::Node { .. })
+                    != matches!(**right, BinarySearchTree::Node { .. });
+                usize::from(is_half) + left.count_half_nodes() + right.count_half_nodes()
+            }
+            BinarySearchTree::Empty => 0,
+        }
+    }
+
+    pub fn level_sums(&self) -> Vec<T>
+    where
+        T: Add<Output = T> + Copy + Default,
+    {
+        let mut sums = Vec::new();
+        let mut level = vec![self];
+
+        while !level.is_empty() {
+            let mut sum = T::default();
+            let mut next_level = Vec::new();
+
+            for node in level {
+                if let BinarySearchTree::Node { value, left, right } = node {
+                    sum = sum + *value;
+                    next_level.push(&**left);
+                    next_level.push(&**right);
+                }
+            }
+
+            if next_level.is_empty() {
+                break;
+            }
+
+            sums.push(sum);
+            level = next_level;
+        }
+
+        sums
+    }
+
+    /// Returns the mean depth of all nodes, with the root at depth 0. An empty tree has no nodes
+    /// to average over and returns 0.0.
+    pub fn average_node_depth(&self) -> f64 {
+        match self.len() {
+            0 => 0.0,
+            len => self.internal_path_length() as f64 / len as f64,
+        }
+    }
+
+    pub fn internal_path_length(&self) -> usize {
+        let mut depth_sum = 0;
+        self.recursive_depth_sum(&mut depth_sum, self, 0);
+        depth_sum
+    }
+
+    fn recursive_depth_sum(&self, depth_sum: &mut usize, root: &BinarySearchTree<T>, depth: usize) {
+        if let BinarySearchTree::Node { left, right, .. } = root {
+            *depth_sum += depth;
+            self.recursive_depth_sum(depth_sum, left, depth + 1);
+            self.recursive_depth_sum(depth_sum, right, depth + 1);
+        }
+    }
+
+    pub fn is_valid_bst(&self) -> bool {
+        self.recursive_is_valid_bst(self, None, None)
+    }
+
+    fn recursive_is_valid_bst(
+        &self,
+        root: &BinarySearchTree<T>,
+        low: Option<&T>,
+        high: Option<&T>,
+    ) -> bool {
+        match root {
+            BinarySearchTree::Node { value, left, right } => {
+                low.is_none_or(|low| value > low)
+                    && high.is_none_or(|high| value < high)
+                    && self.recursive_is_valid_bst(left, low, Some(value))
+                    && self.recursive_is_valid_bst(right, Some(value), high)
+            }
+            BinarySearchTree::Empty => true,
+        }
+    }
+
+    pub fn verify_and_repair(&mut self) -> bool {
+        if self.is_valid_bst() {
+            return false;
+        }
+
+        self.rebuild_sorted_distinct();
+        true
+    }
+
+    pub fn dedup(&mut self) {
+        if !self.is_valid_bst() {
+            self.rebuild_sorted_distinct();
+        }
+    }
+
+    fn rebuild_sorted_distinct(&mut self) {
+        let mut values = mem::take(self).into_sorted_vec();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        values.dedup();
+        let len = values.len();
+        *self = Self::build_balanced(&mut values.into_iter(), len);
+    }
+
+    pub fn diff<'a>(&'a self, other: &'a Self) -> (Vec<&'a T>, Vec<&'a T>) {
+        let mut only_in_self = Vec::new();
+        let mut only_in_other = Vec::new();
+        let mut ours = self.values().into_iter().peekable();
+        let mut theirs = other.values().into_iter().peekable();
+
+        loop {
+            match (ours.peek(), theirs.peek()) {
+                (Some(a), Some(b)) => match a.partial_cmp(b) {
+                    Some(Ordering::Less) => only_in_self.extend(ours.next()),
+                    Some(Ordering::Greater) => only_in_other.extend(theirs.next()),
+                    _ => {
+                        ours.next();
+                        theirs.next();
+                    }
+                },
+                (Some(_), None) => only_in_self.extend(ours.next()),
+                (None, Some(_)) => only_in_other.extend(theirs.next()),
+                (None, None) => break,
+            }
+        }
+
+        (only_in_self, only_in_other)
+    }
+
+    /// Requires `T: Ord` so that `Err` always means an equal value was found; use
+    /// [`try_insert`](Self::try_insert) for values that may be incomparable.
+    pub fn insert_if_absent(&mut self, new_value: T) -> Result<&T, &T>
+    where
+        T: Ord,
+    {
+        match self {
+            BinarySearchTree::Node { value, left, right } => match new_value.cmp(value) {
+                Ordering::Less => left.insert_if_absent(new_value),
+                Ordering::Greater => right.insert_if_absent(new_value),
+                Ordering::Equal => Err(value),
+            },
+            BinarySearchTree::Empty => {
+                *self = BinarySearchTree::Node {
+                    value: new_value,
+                    left: Box::new(BinarySearchTree::Empty),
+                    right: Box::new(BinarySearchTree::Empty),
+                };
+
+                match self {
+                    BinarySearchTree::Node { value, .. } => Ok(value),
+                    BinarySearchTree::Empty => unreachable!(),
+                }
+            }
+        }
+    }
+
+    pub fn dfs(&self, order: DfsOrder) -> DfsIter<'_, T> {
+        DfsIter::new(self, order)
+    }
+
+    pub fn rotate_left(&mut self) -> bool {
+        match mem::take(self) {
+            BinarySearchTree::Node { value, left, right } => match *right {
+                BinarySearchTree::Node { value: pivot, left: pivot_left, right: pivot_right } => {
+                    *self = BinarySearchTree::Node {
+                        value: pivot,
+                        left: Box::new(BinarySearchTree::Node { value, left, right: pivot_left }),
+                        right: pivot_right,
+                    };
+                    true
+                }
+                BinarySearchTree::Empty => {
+                    *self = BinarySearchTree::Node { value, left, right };
+                    false
+                }
+            },
+            BinarySearchTree::Empty => false,
+        }
+    }
+
+    pub fn rotate_right(&mut self) -> bool {
+        match mem::take(self) {
+            BinarySearchTree::Node { value, left, right } => match *left {
+                BinarySearchTree::Node { value: pivot, left: pivot_left, right: pivot_right } => {
+                    *self = BinarySearchTree::Node {
+                        value: pivot,
+                        left: pivot_left,
+                        right: Box::new(BinarySearchTree::Node { value, left: pivot_right, right }),
+                    };
+                    true
+                }
+                BinarySearchTree::Empty => {
+                    *self = BinarySearchTree::Node { value, left, right };
+                    false
+                }
+            },
+            BinarySearchTree::Empty => false,
+        }
+    }
+
+    pub fn contains(&self, target: &T) -> bool {
+        self.contains_counting(target).0
+    }
+
+    pub fn contains_counting(&self, target: &T) -> (bool, usize) {
+        let mut node = self;
+        let mut comparisons = 0;
+
+        while let BinarySearchTree::Node { value, left, right } = node {
+            comparisons += 1;
+
+            match target.partial_cmp(value) {
+                Some(Ordering::Less) => node = left,
+                Some(Ordering::Greater) => node = right,
+                Some(Ordering::Equal) => return (true, comparisons),
+                None => return (false, comparisons),
+            }
+        }
+
+        (false, comparisons)
+    }
+
+    /// Runs `modify` on the value equal to `new_value`, or inserts `new_value` if there is none.
+    /// A value that cannot be compared with the values on its search path, such as NaN, leaves
+    /// the tree unchanged.
+    pub fn modify_or_insert<F: FnOnce(&mut T)>(&mut self, new_value: T, modify: F) {
+        match self {
+            BinarySearchTree::Node { value, left, right } => match new_value.partial_cmp(value) {
+                Some(Ordering::Less) => left.modify_or_insert(new_value, modify),
+                Some(Ordering::Greater) => right.modify_or_insert(new_value, modify),
+                Some(Ordering::Equal) => modify(value),
+                None => {}
+            },
+            BinarySearchTree::Empty => self.insert(new_value),
+        }
+    }
+
+    pub fn truncate_to(&mut self, max: usize) {
+        let mut remaining = max;
+        self.recursive_truncate_to(&mut remaining);
+    }
+
+    fn recursive_truncate_to(&mut self, remaining: &mut usize) {
+        if let BinarySearchTree::Node { left, right, .. } = self {
+            left.recursive_truncate_to(remaining);
+
+            if *remaining == 0 {
+                *self = mem::take(&mut **left);
+            } else {
+                *remaining -= 1;
+                right.recursive_truncate_to(remaining);
+            }
+        }
+    }
+
+    pub fn has_unique_values(&self) -> bool {
+        self.values().windows(2).all(|pair| pair[0].partial_cmp(pair[1]) != Some(Ordering::Equal))
+    }
+
+    pub fn fold_order<B, F>(&self, order: DfsOrder, init: B, f: F) -> B
+    where
+        F: FnMut(B, &T) -> B,
+    {
+        self.dfs(order).fold(init, f)
+    }
+
+    pub fn has_path_sum(&self, target: T) -> bool
+    where
+        T: Add<Output = T> + Copy + PartialEq,
+    {
+        self.recursive_has_path_sum(self, target, None)
+    }
+
+    fn recursive_has_path_sum(&self, root: &BinarySearchTree<T>, target: T, sum: Option<T>) -> bool
+    where
+        T: Add<Output = T> + Copy + PartialEq,
+    {
+        match root {
+            BinarySearchTree::Node { value, left, right } => {
+                let sum = sum.map_or(*value, |sum| sum + *value);
+
+                match (&**left, &**right) {
+                    (BinarySearchTree::Empty, BinarySearchTree::Empty) => sum == target,
+                    _ => {
+                        self.recursive_has_path_sum(left, target, Some(sum))
+                            || self.recursive_has_path_sum(right, target, Some(sum))
+                    }
+                }
+            }
+            BinarySearchTree::Empty => false,
+        }
+    }
+
+    pub fn min_gap(&self) -> Option<T>
+    where
+        T: Sub<Output = T> + PartialOrd + Copy,
+    {
+        let mut min_gap = None;
+
+        for pair in self.values().windows(2) {
+            let gap = *pair[1] - *pair[0];
+
+            if min_gap.is_none_or(|min_gap| gap < min_gap) {
+                min_gap = Some(gap);
+            }
+        }
+
+        min_gap
+    }
+
+    /// Inserts `new_value` and returns the steps taken from the root to where it was placed,
+    /// along with whether it was inserted. `false` means the descent stopped without inserting,
+    /// either at an equal value or at a value `new_value` cannot be compared with, such as NaN;
+    /// the path then leads to that node.
+    pub fn insert_traced(&mut self, new_value: T) -> (Vec<Direction>, bool) {
+        let mut path = Vec::new();
+        let mut node = self;
+
+        loop {
+            match node {
+                BinarySearchTree::Node { value, left, right } => match new_value.partial_cmp(value)
+                {
+                    Some(Ordering::Less) => {
+                        path.push(Direction::Left);
+                        node = left;
+                    }
+                    Some(Ordering::Greater) => {
+                        path.push(Direction::Right);
+                        node = right;
+                    }
+                    Some(Ordering::Equal) | None => return (path, false),
+                },
+                BinarySearchTree::Empty => {
+                    node.insert(new_value);
+                    return (path, true);
+                }
+            }
+        }
+    }
+
+    pub fn count_less(&self, target: &T) -> usize {
+        match self {
+            BinarySearchTree::Node { value, left, right } => {
+                if value < target {
+                    left.len() + 1 + right.count_less(target)
+                } else {
+                    left.count_less(target)
+                }
+            }
+            BinarySearchTree::Empty => 0,
+        }
+    }
+
+    pub fn count_greater(&self, target: &T) -> usize {
+        match self {
+            BinarySearchTree::Node { value, left, right } => {
+                if value > target {
+                    right.len() + 1 + left.count_greater(target)
+                } else {
+                    right.count_greater(target)
+                }
+            }
+            BinarySearchTree::Empty => 0,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        match self {
+            BinarySearchTree::Node { left, right, .. } => 1 + left.height().max(right.height()),
+            BinarySearchTree::Empty => 0,
+        }
+    }
+
+    pub fn rebuild_randomized(&mut self, seed: u64) {
+        let mut values = mem::take(self).into_sorted_vec();
+        SplitMix64::new(seed).shuffle(&mut values);
+
+        for value in values {
+            self.insert(value);
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self)
+    }
+
+    pub fn subtree_at(&self, target: &T) -> Option<&BinarySearchTree<T>> {
+        let mut node = self;
+
+        while let BinarySearchTree::Node { value, left, right } = node {
+            match target.partial_cmp(value) {
+                Some(Ordering::Less) => node = left,
+                Some(Ordering::Greater) => node = right,
+                Some(Ordering::Equal) => return Some(node),
+                None => return None,
+            }
+        }
+
+        None
+    }
+
+    pub fn depth_histogram(&self) -> Vec<usize> {
+        let mut histogram = Vec::new();
+        self.recursive_depth_histogram(&mut histogram, self, 0);
+        histogram
+    }
+
+    fn recursive_depth_histogram(
+        &self,
+        histogram: &mut Vec<usize>,
+        root: &BinarySearchTree<T>,
+        depth: usize,
+    ) {
+        if let BinarySearchTree::Node { left, right, .. } = root {
+            if histogram.len() == depth {
+                histogram.push(0);
+            }
+
+            histogram[depth] += 1;
+            self.recursive_depth_histogram(histogram, left, depth + 1);
+            self.recursive_depth_histogram(histogram, right, depth + 1);
+        }
+    }
+
+    pub fn prune_leaves(&mut self) -> usize {
+        match self {
+            BinarySearchTree::Node { left, right, .. } => {
+                if let (BinarySearchTree::Empty, BinarySearchTree::Empty) = (&**left, &**right) {
+                    *self = BinarySearchTree::Empty;
+                    1
+                } else {
+                    left.prune_leaves() + right.prune_leaves()
+                }
+            }
+            BinarySearchTree::Empty => 0,
+        }
+    }
+
+    /// Applies `f` to every value in sorted order. `f` must be strictly monotonic (it must keep
+    /// the relative order of any two values), otherwise the tree is no longer a valid BST.
+    pub fn map_in_place<F: Fn(&mut T)>(&mut self, f: F) {
+        self.recursive_map_in_place(&f);
+    }
+
+    fn recursive_map_in_place<F: Fn(&mut T)>(&mut self, f: &F) {
+        if let BinarySearchTree::Node { value, left, right } = self {
+            left.recursive_map_in_place(f);
+            f(value);
+            right.recursive_map_in_place(f);
+        }
+    }
+
+    pub fn try_insert(&mut self, new_value: T) -> Result<bool, IncomparableError> {
+        match self {
+            BinarySearchTree::Node { value, left, right } => match new_value.partial_cmp(value) {
+                Some(Ordering::Less) => left.try_insert(new_value),
+                Some(Ordering::Greater) => right.try_insert(new_value),
+                Some(Ordering::Equal) => Ok(false),
+                None => Err(IncomparableError),
+            },
+            BinarySearchTree::Empty => {
+                if new_value.partial_cmp(&new_value).is_none() {
+                    return Err(IncomparableError);
+                }
+
+                self.insert(new_value);
+                Ok(true)
+            }
+        }
+    }
+
+    /// Inserts like [`insert`](Self::insert) and returns the number of comparisons made. The
+    /// count still includes the final comparison when the value is equal to, or incomparable
+    /// with, an existing one and nothing is inserted.
+    pub(crate) fn insert_counting(&mut self, new_value: T) -> usize {
+        let mut node = self;
+        let mut comparisons = 0;
+
+        while let BinarySearchTree::Node { value, left, right } = node {
+            comparisons += 1;
+
+            match new_value.partial_cmp(value) {
+                Some(Ordering::Less) => node = left,
+                Some(Ordering::Greater) => node = right,
+                Some(Ordering::Equal) | None => return comparisons,
+            }
+        }
+
+        node.insert(new_value);
+        comparisons
+    }
+
+    /// Inserts like [`insert_traced`](Self::insert_traced), then rebuilds the deepest
+    /// weight-unbalanced ancestor if the new node sits deeper than `log_{3/2}(len)`, where `len` is
+    /// the size of the tree after the insert. This keeps the height logarithmic in amortized
+    /// O(log n) per insert, without storing sizes in the nodes.
+    pub(crate) fn insert_scapegoat(&mut self, new_value: T, len: usize) -> bool {
+        let (path, inserted) = self.insert_traced(new_value);
+
+        if !inserted || path.len() as f64 <= (len as f64).log(1.5).floor() {
+            return inserted;
+        }
+
+        let mut nodes = vec![&*self];
+
+        for direction in &path {
+            if let Some(BinarySearchTree::Node { left, right, .. }) = nodes.last() {
+                nodes.push(match direction {
+                    Direction::Left => left,
+                    Direction::Right => right,
+                });
+            }
+        }
+
+        let mut size = 1;
+        let mut scapegoat = None;
+
+        for depth in (0..path.len()).rev() {
+            if let BinarySearchTree::Node { left, right, .. } = nodes[depth] {
+                let sibling = match path[depth] {
+                    Direction::Left => right,
+                    Direction::Right => left,
+                };
+                let child_size = size;
+                size += 1 + sibling.len();
+
+                if 3 * child_size > 2 * size {
+                    scapegoat = Some(depth);
+                    break;
+                }
+            }
+        }
+
+        if let Some(depth) = scapegoat {
+            let mut node = self;
+
+            for direction in &path[..depth] {
+                if let BinarySearchTree::Node { left, right, .. } = node {
+                    node = match direction {
+                        Direction::Left => left,
+                        Direction::Right => right,
+                    };
+                }
+            }
+
+            *node = mem::take(node).into_balanced();
+        }
+
+        true
+    }
+
+    pub fn filter<F: Fn(&T) -> bool>(&self, pred: F) -> Vec<&T> {
+        self.iter().filter(|value| pred(value)).collect()
+    }
+
+    pub fn content_fingerprint(&self) -> u64
+    where
+        T: Hash,
+    {
+        let mut hasher = DefaultHasher::new();
+
+        for value in self.iter() {
+            value.hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    pub fn contains_sorted_batch(&self, sorted_queries: &[T]) -> Vec<bool> {
+        let mut values = self.iter().peekable();
+
+        sorted_queries
+            .iter()
+            .map(|query| {
+                while values.next_if(|value| *value < query).is_some() {}
+                values.peek().is_some_and(|value| *value == query)
+            })
+            .collect()
+    }
+
+    pub fn is_perfect(&self) -> bool {
+        self.perfect_prefix_levels() == self.height()
+    }
+
+    pub fn perfect_prefix_levels(&self) -> usize {
+        match self {
+            BinarySearchTree::Node { left, right, .. } => {
+                1 + left.perfect_prefix_levels().min(right.perfect_prefix_levels())
+            }
+            BinarySearchTree::Empty => 0,
+        }
+    }
+
+    fn merge_sorted_distinct(ours: Vec<T>, theirs: Vec<T>) -> Vec<T> {
+        let mut merged = Vec::with_capacity(ours.len() + theirs.len());
+        let mut ours = ours.into_iter().peekable();
+        let mut theirs = theirs.into_iter().peekable();
+
+        loop {
+            let next = match (ours.peek(), theirs.peek()) {
+                (Some(a), Some(b)) => match a.partial_cmp(b) {
+                    Some(Ordering::Greater) => theirs.next(),
+                    Some(Ordering::Less) => ours.next(),
+                    _ => {
+                        theirs.next();
+                        ours.next()
+                    }
+                },
+                (Some(_), None) => ours.next(),
+                (None, Some(_)) => theirs.next(),
+                (None, None) => break,
+            };
+
+            if let Some(value) = next {
+                if merged.last() != Some(&value) {
+                    merged.push(value);
+                }
+            }
+        }
+
+        merged
+    }
+
+    pub fn insert_balanced_batch(&mut self, mut values: Vec<T>) {
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let merged = Self::merge_sorted_distinct(mem::take(self).into_sorted_vec(), values);
+        let len = merged.len();
+        *self = Self::build_balanced(&mut merged.into_iter(), len);
+    }
+
+    /// Lays the tree out in a level-order array in which the children of index `i` sit at
+    /// `2i + 1` and `2i + 2`. The array grows exponentially with the height, so a degenerate tree
+    /// of height `h` needs `2^h - 1` slots. Returns `None` if more than `max_len` slots, or more
+    /// than a `Vec` can hold, would be needed.
+    pub fn to_level_order_array(&self, max_len: usize) -> Option<Vec<Option<&T>>> {
+        let max_len = max_len.min(isize::MAX as usize / mem::size_of::<Option<&T>>());
+        let len = self.recursive_level_order_len(self, 0, max_len)?;
+        let mut array = vec![None; len];
+        self.recursive_to_level_order_array(&mut array, self, 0);
+        Some(array)
+    }
+
+    fn recursive_level_order_len(
+        &self,
+        root: &BinarySearchTree<T>,
+        index: usize,
+        max_len: usize,
+    ) -> Option<usize> {
+        match root {
+            BinarySearchTree::Node { left, right, .. } => {
+                if index >= max_len {
+                    return None;
+                }
+
+                let left_index = index.checked_mul(2)?.checked_add(1)?;
+                let left_len = self.recursive_level_order_len(left, left_index, max_len)?;
+                let right_len =
+                    self.recursive_level_order_len(right, left_index.checked_add(1)?, max_len)?;
+                Some((index + 1).max(left_len).max(right_len))
+            }
+            BinarySearchTree::Empty => Some(0),
+        }
+    }
+
+    fn recursive_to_level_order_array<'a>(
+        &self,
+        array: &mut [Option<&'a T>],
+        root: &'a BinarySearchTree<T>,
+        index: usize,
+    ) {
+        if let BinarySearchTree::Node { value, left, right } = root {
+            array[index] = Some(value);
+            self.recursive_to_level_order_array(array, left, 2 * index + 1);
+            self.recursive_to_level_order_array(array, right, 2 * index + 2);
+        }
+    }
+
+    /// Rebuilds the exact shape described by a level-order array in which the children of index
+    /// `i` sit at `2i + 1` and `2i + 2`. The ordering of the values is not checked, so this can
+    /// produce a tree that is not a valid BST.
+    pub fn from_level_order_array(values: &[Option<T>]) -> Self
+    where
+        T: Clone,
+    {
+        Self::recursive_from_level_order_array(values, 0)
+    }
+
+    fn recursive_from_level_order_array(values: &[Option<T>], index: usize) -> Self
+    where
+        T: Clone,
+    {
+        match values.get(index) {
+            Some(Some(value)) => Self::node(
+                value.clone(),
+                Self::recursive_from_level_order_array(values, 2 * index + 1),
+                Self::recursive_from_level_order_array(values, 2 * index + 2),
+            ),
+            _ => BinarySearchTree::Empty,
+        }
+    }
+
+    pub fn count_occurrences_in_range(&self, low: &T, high: &T) -> usize {
+        match self {
+            BinarySearchTree::Node { value, left, right } => {
+                if value < low {
+                    right.count_occurrences_in_range(low, high)
+                } else if value > high {
+                    left.count_occurrences_in_range(low, high)
+                } else {
+                    1 + left.count_occurrences_in_range(low, high)
+                        + right.count_occurrences_in_range(low, high)
+                }
+            }
+            BinarySearchTree::Empty => 0,
+        }
+    }
+
+    pub fn remove_subtree(&mut self, root_value: &T) -> Option<BinarySearchTree<T>> {
+        match self {
+            BinarySearchTree::Node { value, left, right } => match root_value.partial_cmp(value) {
+                Some(Ordering::Less) => left.remove_subtree(root_value),
+                Some(Ordering::Greater) => right.remove_subtree(root_value),
+                Some(Ordering::Equal) => Some(mem::take(self)),
+                None => None,
+            },
+            BinarySearchTree::Empty => None,
+        }
+    }
+
+    pub fn graft(&mut self, subtree: BinarySearchTree<T>) -> Result<(), BinarySearchTree<T>> {
+        match subtree {
+            BinarySearchTree::Empty => Ok(()),
+            subtree if subtree.is_valid_bst() => self.recursive_graft(subtree),
+            subtree => Err(subtree),
+        }
+    }
+
+    fn recursive_graft(&mut self, subtree: BinarySearchTree<T>) -> Result<(), BinarySearchTree<T>> {
+        match self {
+            BinarySearchTree::Node { value, left, right } => {
+                if subtree.max().is_some_and(|max| max < value) {
+                    left.recursive_graft(subtree)
+                } else if subtree.min().is_some_and(|min| min > value) {
+                    right.recursive_graft(subtree)
+                } else {
+                    Err(subtree)
+                }
+            }
+            BinarySearchTree::Empty => {
+                *self = subtree;
+                Ok(())
+            }
+        }
+    }
+
+    pub fn ordering_violations(&self) -> Vec<(&T, &T)> {
+        let mut violations = Vec::new();
+        self.recursive_ordering_violations(&mut violations, &mut Vec::new(), self);
+        violations
+    }
+
+    fn recursive_ordering_violations<'a>(
+        &self,
+        violations: &mut Vec<(&'a T, &'a T)>,
+        ancestors: &mut Vec<(&'a T, Direction)>,
+        root: &'a BinarySearchTree<T>,
+    ) {
+        if let BinarySearchTree::Node { value, left, right } = root {
+            for &(ancestor, direction) in ancestors.iter() {
+                let ordered = match direction {
+                    Direction::Left => value < ancestor,
+                    Direction::Right => value > ancestor,
+                };
+
+                if !ordered {
+                    violations.push((ancestor, value));
+                }
+            }
+
+            ancestors.push((value, Direction::Left));
+            self.recursive_ordering_violations(violations, ancestors, left);
+            ancestors.pop();
+            ancestors.push((value, Direction::Right));
+            self.recursive_ordering_violations(violations, ancestors, right);
+            ancestors.pop();
+        }
+    }
+
+    pub fn most_frequent(&self) -> Option<(&T, usize)> {
+        let mut most_frequent: Option<(&T, usize)> = None;
+        let mut run: Option<(&T, usize)> = None;
+
+        for value in self.iter() {
+            run = match run {
+                Some((current, count)) if current == value => Some((current, count + 1)),
+                _ => Some((value, 1)),
+            };
+
+            if let Some((current, count)) = run {
+                if most_frequent.is_none_or(|(_, best)| count > best) {
+                    most_frequent = Some((current, count));
+                }
+            }
+        }
+
+        most_frequent
+    }
+
+    pub fn leaves(&self) -> Vec<&T> {
+        let mut v = Vec::new();
+        self.recursive_leaves(&mut v, self);
+        v
+    }
+
+    fn recursive_leaves<'a>(&self, v: &mut Vec<&'a T>, root: &'a BinarySearchTree<T>) {
+        if let BinarySearchTree::Node { value, left, right } = root {
+            if let (BinarySearchTree::Empty, BinarySearchTree::Empty) = (&**left, &**right) {
+                v.push(value);
+            }
+
+            self.recursive_leaves(v, left);
+            self.recursive_leaves(v, right);
+        }
+    }
+
+    pub fn iter_leaves(&self) -> impl Iterator<Item = &T> {
+        let mut stack = vec![self];
+
+        std::iter::from_fn(move || {
+            while let Some(node) = stack.pop() {
+                if let BinarySearchTree::Node { value, left, right } = node {
+                    if let (BinarySearchTree::Empty, BinarySearchTree::Empty) = (&**left, &**right)
+                    {
+                        return Some(value);
+                    }
+
+                    stack.push(right);
+                    stack.push(left);
+                }
+            }
+
+            None
+        })
+    }
+
+    pub fn same_shape<U>(&self, other: &BinarySearchTree<U>) -> bool
+    where
+        U: fmt::Debug + PartialOrd,
+    {
+        match (self, other) {
+            (
+                BinarySearchTree::Node { left, right, .. },
+                BinarySearchTree::Node { left: other_left, right: other_right, .. },
+            ) => left.same_shape(other_left) && right.same_shape(other_right),
+            (BinarySearchTree::Empty, BinarySearchTree::Empty) => true,
+            _ => false,
+        }
+    }
+
+    /// Counts downward paths (starting at any node and ending at any of its descendants or itself)
+    /// whose values sum to `target`. `T` is not required to be hashable, so the running prefix
+    /// sums of the current path are scanned linearly, costing O(n·h).
+    pub fn count_paths_with_sum(&self, target: T) -> usize
+    where
+        T: Add<Output = T> + Sub<Output = T> + Copy + PartialEq,
+    {
+        self.recursive_count_paths_with_sum(self, target, &mut Vec::new())
+    }
+
+    fn recursive_count_paths_with_sum(
+        &self,
+        root: &BinarySearchTree<T>,
+        target: T,
+        prefixes: &mut Vec<T>,
+    ) -> usize
+    where
+        T: Add<Output = T> + Sub<Output = T> + Copy + PartialEq,
+    {
+        match root {
+            BinarySearchTree::Node { value, left, right } => {
+                let prefix = prefixes.last().map_or(*value, |last| *last + *value);
+                let count = usize::from(prefix == target)
+                    + prefixes.iter().filter(|earlier| prefix - **earlier == target).count();
+
+                prefixes.push(prefix);
+                let count = count
+                    + self.recursive_count_paths_with_sum(left, target, prefixes)
+                    + self.recursive_count_paths_with_sum(right, target, prefixes);
+                prefixes.pop();
+                count
+            }
+            BinarySearchTree::Empty => 0,
+        }
+    }
+
+    pub fn ranked_values(&self) -> Vec<(usize, &T)> {
+        self.iter().enumerate().collect()
+    }
+
+    pub fn merge_balanced(a: BinarySearchTree<T>, b: BinarySearchTree<T>) -> BinarySearchTree<T> {
+        let merged = Self::merge_sorted_distinct(a.into_sorted_vec(), b.into_sorted_vec());
+        let len = merged.len();
+        Self::build_balanced(&mut merged.into_iter(), len)
+    }
+
+    pub fn in_order_small(&self) -> SmallValues<'_, T> {
+        match self {
+            BinarySearchTree::Node { value, left, right } => match (&**left, &**right) {
+                (BinarySearchTree::Empty, BinarySearchTree::Empty) => SmallValues::One([value]),
+                _ => SmallValues::Many(self.values()),
+            },
+            BinarySearchTree::Empty => SmallValues::Empty,
+        }
+    }
+
+    pub fn is_symmetric(&self) -> bool {
+        match self {
+            BinarySearchTree::Node { left, right, .. } => left.is_mirror_shape_of(right),
+            BinarySearchTree::Empty => true,
+        }
+    }
+
+    fn is_mirror_shape_of(&self, other: &BinarySearchTree<T>) -> bool {
+        match (self, other) {
+            (
+                BinarySearchTree::Node { left, right, .. },
+                BinarySearchTree::Node { left: other_left, right: other_right, .. },
+            ) => left.is_mirror_shape_of(other_right) && right.is_mirror_shape_of(other_left),
+            (BinarySearchTree::Empty, BinarySearchTree::Empty) => true,
+            _ => false,
+        }
+    }
+
+    pub fn partition<F: Fn(&T) -> bool>(&self, pred: F) -> (Vec<&T>, Vec<&T>) {
+        self.iter().partition(|value| pred(value))
+    }
+
+    pub fn has_close_pair(&self, max_gap: T) -> bool
+    where
+        T: Sub<Output = T> + PartialOrd + Copy,
+    {
+        self.min_gap().is_some_and(|min_gap| min_gap <= max_gap)
+    }
+
+    pub fn sample(&self, k: usize, seed: u64) -> Vec<&T> {
+        let mut rng = SplitMix64::new(seed);
+        let mut reservoir = Vec::with_capacity(k);
+
+        for (seen, value) in self.iter().enumerate() {
+            if reservoir.len() < k {
+                reservoir.push(value);
+            } else {
+                let index = rng.below(seen + 1);
+
+                if index < k {
+                    reservoir[index] = value;
+                }
+            }
+        }
+
+        reservoir
+    }
+
+    pub fn peek_min(&self) -> Option<&T> {
+        self.min()
+    }
+
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max()
+    }
+
+    pub fn distinct_len(&self) -> usize {
+        let mut previous = None;
+        let mut distinct = 0;
+
+        for value in self.iter() {
+            if previous != Some(value) {
+                distinct += 1;
+                previous = Some(value);
+            }
+        }
+
+        distinct
+    }
+
+    /// Builds a balanced tree in O(n). The iterator must yield strictly increasing values. When
+    /// its size hint is exact the values are placed directly; otherwise they are collected first.
+    /// A size hint that turns out to be wrong only costs a second pass, never values.
+    pub fn from_sorted_iter(iter: impl Iterator<Item = T>) -> Self {
+        let mut iter = iter;
+
+        if let (low, Some(high)) = iter.size_hint() {
+            if low == high {
+                let tree = Self::build_balanced(&mut iter, low);
+
+                match iter.next() {
+                    None if tree.len() == low => return tree,
+                    next => {
+                        let mut values = tree.into_sorted_vec();
+                        values.extend(next);
+                        values.extend(iter);
+                        let len = values.len();
+                        return Self::build_balanced(&mut values.into_iter(), len);
+                    }
+                }
+            }
+        }
+
+        let values = iter.collect::<Vec<_>>();
+        let len = values.len();
+        Self::build_balanced(&mut values.into_iter(), len)
+    }
+
+    pub fn splay(&mut self, target: &T) -> bool {
+        self.recursive_splay(target);
+
+        match self {
+            BinarySearchTree::Node { value, .. } => {
+                target.partial_cmp(value) == Some(Ordering::Equal)
+            }
+            BinarySearchTree::Empty => false,
+        }
+    }
+
+    fn recursive_splay(&mut self, target: &T) {
+        let BinarySearchTree::Node { value, left, right } = self else {
+            return;
+        };
+
+        match target.partial_cmp(value) {
+            Some(Ordering::Less) => {
+                let BinarySearchTree::Node { value: child, left: grand_left, right: grand_right } =
+                    &mut **left
+                else {
+                    return;
+                };
+
+                match target.partial_cmp(child) {
+                    Some(Ordering::Less) => {
+                        grand_left.recursive_splay(target);
+                        self.rotate_right();
+                    }
+                    Some(Ordering::Greater) => {
+                        grand_right.recursive_splay(target);
+                        left.rotate_left();
+                    }
+                    _ => {}
+                }
+
+                self.rotate_right();
+            }
+            Some(Ordering::Greater) => {
+                let BinarySearchTree::Node { value: child, left: grand_left, right: grand_right } =
+                    &mut **right
+                else {
+                    return;
+                };
+
+                match target.partial_cmp(child) {
+                    Some(Ordering::Greater) => {
+                        grand_right.recursive_splay(target);
+                        self.rotate_left();
+                    }
+                    Some(Ordering::Less) => {
+                        grand_left.recursive_splay(target);
+                        right.rotate_right();
+                    }
+                    _ => {}
+                }
+
+                self.rotate_left();
+            }
+            _ => {}
+        }
+    }
+
+    pub fn group_by<K: Eq + Hash, F: Fn(&T) -> K>(&self, key: F) -> HashMap<K, Vec<&T>> {
+        let mut groups: HashMap<K, Vec<&T>> = HashMap::new();
+
+        for value in self.iter() {
+            groups.entry(key(value)).or_default().push(value);
+        }
+
+        groups
+    }
+
+    pub fn satisfies_min_heap(&self) -> bool {
+        match self {
+            BinarySearchTree::Node { value, left, right } => {
+                [left, right].into_iter().all(|child| match &**child {
+                    BinarySearchTree::Node { value: child_value, .. } => {
+                        value <= child_value && child.satisfies_min_heap()
+                    }
+                    BinarySearchTree::Empty => true,
+                })
+            }
+            BinarySearchTree::Empty => true,
+        }
+    }
+
+    pub fn ancestors(&self, target: &T) -> Option<Vec<&T>> {
+        let mut ancestors = Vec::new();
+        let mut node = self;
+
+        while let BinarySearchTree::Node { value, left, right } = node {
+            match target.partial_cmp(value) {
+                Some(Ordering::Less) => node = left,
+                Some(Ordering::Greater) => node = right,
+                Some(Ordering::Equal) => {
+                    ancestors.reverse();
+                    return Some(ancestors);
+                }
+                None => return None,
+            }
+
+            ancestors.push(value);
+        }
+
+        None
+    }
+
+    pub fn subtree_size(&self, root_value: &T) -> Option<usize> {
+        self.subtree_at(root_value).map(BinarySearchTree::len)
+    }
+
+    pub fn find_first<F: Fn(&T) -> bool>(&self, pred: F) -> Option<&T> {
+        let mut stack = Vec::new();
+        let mut node = self;
+
+        loop {
+            while let BinarySearchTree::Node { value, left, right } = node {
+                stack.push((value, right));
+                node = left;
+            }
+
+            let (value, right) = stack.pop()?;
+
+            if pred(value) {
+                return Some(value);
+            }
+
+            node = right;
+        }
+    }
+
+    /// Writes the tree in Newick format, e.g. `((1,37)12,(84,100)90)60;`. A node with a single
+    /// child keeps an empty label in place of the missing one, as in `(1,)4`, so left and right
+    /// children stay distinguishable. Labels that are empty or contain whitespace or any of
+    /// `()[]',:;` are single-quoted, with `'` written as `''`.
+    pub fn to_newick(&self) -> String
+    where
+        T: fmt::Display,
+    {
+        let mut newick = String::new();
+        self.recursive_to_newick(&mut newick, self);
+        newick.push(';');
+        newick
+    }
+
+    fn recursive_to_newick(&self, newick: &mut String, root: &BinarySearchTree<T>)
+    where
+        T: fmt::Display,
+    {
+        if let BinarySearchTree::Node { value, left, right } = root {
+            if matches!(**left, BinarySearchTree::Node { .. })
+                || matches!(**right, BinarySearchTree::Node { .. })
+            {
+                newick.push('(');
+                self.recursive_to_newick(newick, left);
+                newick.push(',');
+                self.recursive_to_newick(newick, right);
+                newick.push(')');
+            }
+
+            let label = value.to_string();
+
+            if label.is_empty()
+                || label.contains(|c: char| "()[]',:;".contains(c) || c.is_whitespace())
+            {
+                newick.push('\'');
+                newick.push_str(&label.replace('\'', "''"));
+                newick.push('\'');
+            } else {
+                newick.push_str(&label);
+            }
+        }
+    }
+
+    /// Parses the format written by [`to_newick`](Self::to_newick). Whitespace between tokens, a
+    /// trailing `;` and single-quoted labels (with `''` for a literal quote) are accepted. The
+    /// shape is rebuilt exactly as written and the values are not re-sorted, so the result may
+    /// not be a valid BST. Parentheses nested deeper than [`MAX_NEWICK_DEPTH`] are rejected.
+    pub fn from_newick(s: &str) -> Result<Self, ParseError>
+    where
+        T: FromStr,
+    {
+        let mut position = 0;
+        let tree = Self::recursive_from_newick(s, &mut position, 0)?;
+
+        if s[position..].starts_with(';') {
+            position += 1;
+            Self::skip_newick_whitespace(s, &mut position);
+        }
+
+        match s[position..].chars().next() {
+            Some(found) => Err(ParseError::UnexpectedChar { position, found }),
+            None => Ok(tree),
+        }
+    }
+
+    fn recursive_from_newick(
+        s: &str,
+        position: &mut usize,
+        depth: usize,
+    ) -> Result<Self, ParseError>
+    where
+        T: FromStr,
+    {
+        Self::skip_newick_whitespace(s, position);
+
+        let (left, right) = if s[*position..].starts_with('(') {
+            if depth >= MAX_NEWICK_DEPTH {
+                return Err(ParseError::TooDeep { position: *position });
+            }
+
+            *position += 1;
+            let left = Self::recursive_from_newick(s, position, depth + 1)?;
+            Self::expect_newick_char(s, position, ',')?;
+            let right = Self::recursive_from_newick(s, position, depth + 1)?;
+            Self::expect_newick_char(s, position, ')')?;
+            Self::skip_newick_whitespace(s, position);
+            (Some(left), right)
+        } else {
+            (None, BinarySearchTree::Empty)
+        };
+
+        let start = *position;
+        let label = Self::newick_label(s, position)?;
+        Self::skip_newick_whitespace(s, position);
+
+        match (label, left) {
+            (None, None) => Ok(BinarySearchTree::Empty),
+            (None, Some(_)) => Err(ParseError::MissingLabel { position: start }),
+            (Some(label), left) => match label.parse() {
+                Ok(value) => Ok(Self::node(value, left.unwrap_or_default(), right)),
+                Err(_) => Err(ParseError::InvalidLabel { position: start, label }),
+            },
+        }
+    }
+
+    fn newick_label(s: &str, position: &mut usize) -> Result<Option<String>, ParseError> {
+        let rest = &s[*position..];
+
+        if let Some(quoted) = rest.strip_prefix('\'') {
+            let mut label = String::new();
+            let mut chars = quoted.char_indices().peekable();
+
+            while let Some((index, c)) = chars.next() {
+                if c != '\'' {
+                    label.push(c);
+                } else if matches!(chars.peek(), Some((_, '\''))) {
+                    chars.next();
+                    label.push('\'');
+                } else {
+                    *position += index + 2;
+                    return Ok(Some(label));
+                }
+            }
+
+            return Err(ParseError::UnexpectedEnd);
+        }
+
+        let len = rest
+            .find(|c: char| matches!(c, '(' | ')' | ',' | ';') || c.is_whitespace())
+            .unwrap_or(rest.len());
+        *position += len;
+        Ok((len > 0).then(|| rest[..len].to_string()))
+    }
+
+    fn skip_newick_whitespace(s: &str, position: &mut usize) {
+        *position += s[*position..].len() - s[*position..].trim_start().len();
+    }
+
+    fn expect_newick_char(s: &str, position: &mut usize, expected: char) -> Result<(), ParseError> {
+        match s[*position..].chars().next() {
+            Some(found) if found == expected => {
+                *position += 1;
+                Ok(())
+            }
+            Some(found) => Err(ParseError::UnexpectedChar { position: *position, found }),
+            None => Err(ParseError::UnexpectedEnd),
+        }
+    }
+
+    pub fn distance_to_nearest_leaf(&self, target: &T) -> Option<usize> {
+        self.subtree_at(target).map(|subtree| subtree.recursive_distance_to_nearest_leaf())
+    }
+
+    fn recursive_distance_to_nearest_leaf(&self) -> usize {
+        match self {
+            BinarySearchTree::Node { left, right, .. } => match (&**left, &**right) {
+                (BinarySearchTree::Empty, BinarySearchTree::Empty) => 0,
+                (BinarySearchTree::Empty, child) | (child, BinarySearchTree::Empty) => {
+                    1 + child.recursive_distance_to_nearest_leaf()
+                }
+                (left, right) => {
+                    1 + left
+                        .recursive_distance_to_nearest_leaf()
+                        .min(right.recursive_distance_to_nearest_leaf())
+                }
+            },
+            BinarySearchTree::Empty => 0,
+        }
+    }
+
+    pub fn batch_rank(&self, sorted_targets: &[T]) -> Vec<usize> {
+        let mut values = self.iter().peekable();
+        let mut rank = 0;
+
+        sorted_targets
+            .iter()
+            .map(|target| {
+                while values.next_if(|value| *value < target).is_some() {
+                    rank += 1;
+                }
+
+                rank
+            })
+            .collect()
+    }
+
+    pub fn rebalance_toward(&mut self, policy: BalancePolicy) {
+        match policy {
+            BalancePolicy::MinHeight => *self = mem::take(self).into_balanced(),
+            BalancePolicy::Random(seed) => self.rebuild_randomized(seed),
+        }
+    }
+
+    /// Yields mutable references to the values in `[low, high]` in sorted order. Callers must not
+    /// change the part of a value that determines its ordering.
+    pub fn range_mut(&mut self, low: &T, high: &T) -> impl Iterator<Item = &mut T> {
+        let mut v = Vec::new();
+        Self::recursive_range_mut(&mut v, self, low, high);
+        v.into_iter()
+    }
+
+    fn recursive_range_mut<'a>(
+        v: &mut Vec<&'a mut T>,
+        root: &'a mut BinarySearchTree<T>,
+        low: &T,
+        high: &T,
+    ) {
+        if let BinarySearchTree::Node { value, left, right } = root {
+            let above_low = *value >= *low;
+            let below_high = *value <= *high;
+
+            if above_low {
+                Self::recursive_range_mut(v, left, low, high);
+            }
+
+            if above_low && below_high {
+                v.push(value);
+            }
+
+            if below_high {
+                Self::recursive_range_mut(v, right, low, high);
+            }
+        }
+    }
+
+    /// Compares the number of nodes under the root's left and right children. Only the root is
+    /// considered, which is enough to tell whether values arrived mostly ascending or descending.
+    pub fn skew(&self) -> Skew {
+        match self {
+            BinarySearchTree::Node { left, right, .. } => match left.len().cmp(&right.len()) {
+                Ordering::Greater => Skew::LeftHeavy,
+                Ordering::Less => Skew::RightHeavy,
+                Ordering::Equal => Skew::Balanced,
+            },
+            BinarySearchTree::Empty => Skew::Balanced,
+        }
+    }
+
+    pub fn remove_range(&mut self, low: &T, high: &T) -> usize {
+        match self {
+            BinarySearchTree::Node { value, left, right } => {
+                if *value < *low {
+                    right.remove_range(low, high)
+                } else if *value > *high {
+                    left.remove_range(low, high)
+                } else {
+                    let removed = left.remove_range(low, high) + right.remove_range(low, high);
+                    self.remove_root();
+                    removed + 1
+                }
+            }
+            BinarySearchTree::Empty => 0,
+        }
+    }
+
+    pub fn into_balanced(self) -> Self {
+        let values = self.into_sorted_vec();
+        let len = values.len();
+        Self::build_balanced(&mut values.into_iter(), len)
+    }
+
+    pub fn range_counting(&self, low: &T, high: &T) -> (Vec<&T>, usize) {
+        let mut v = Vec::new();
+        let visited = self.recursive_range_counting(&mut v, self, low, high);
+        (v, visited)
+    }
+
+    fn recursive_range_counting<'a>(
+        &self,
+        v: &mut Vec<&'a T>,
+        root: &'a BinarySearchTree<T>,
+        low: &T,
+        high: &T,
+    ) -> usize {
+        match root {
+            BinarySearchTree::Node { value, left, right } => {
+                let mut visited = 1;
+
+                if value >= low {
+                    visited += self.recursive_range_counting(v, left, low, high);
+                }
+
+                if value >= low && value <= high {
+                    v.push(value);
+                }
+
+                if value <= high {
+                    visited += self.recursive_range_counting(v, right, low, high);
+                }
+
+                visited
+            }
+            BinarySearchTree::Empty => 0,
+        }
+    }
+
+    pub fn merge_iter<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> {
+        let mut ours = self.iter().peekable();
+        let mut theirs = other.iter().peekable();
+        let mut last: Option<&T> = None;
+
+        std::iter::from_fn(move || loop {
+            let next = match (ours.peek(), theirs.peek()) {
+                (Some(a), Some(b)) => match a.partial_cmp(b) {
+                    Some(Ordering::Greater) => theirs.next(),
+                    Some(Ordering::Less) => ours.next(),
+                    _ => {
+                        theirs.next();
+                        ours.next()
+                    }
+                },
+                (Some(_), None) => ours.next(),
+                (None, Some(_)) => theirs.next(),
+                (None, None) => return None,
+            };
+
+            if let Some(value) = next {
+                if last != Some(value) {
+                    last = Some(value);
+                    return Some(value);
+                }
+            }
+        })
+    }
+
+    /// Returns the 0-based sorted position of `target`, or `None` if it is absent. Subtree sizes
+    /// are not cached, so each step to the right counts the skipped left subtree.
+    pub fn index_of(&self, target: &T) -> Option<usize> {
+        let mut node = self;
+        let mut index = 0;
+
+        while let BinarySearchTree::Node { value, left, right } = node {
+            match target.partial_cmp(value) {
+                Some(Ordering::Less) => node = left,
+                Some(Ordering::Greater) => {
+                    index += left.len() + 1;
+                    node = right;
+                }
+                Some(Ordering::Equal) => return Some(index + left.len()),
+                None => return None,
+            }
+        }
+
+        None
+    }
+
+    pub fn balance_factors(&self) -> Vec<(&T, i32)> {
+        let mut v = Vec::new();
+        self.recursive_balance_factors(&mut v, self);
+        v
+    }
+
+    fn recursive_balance_factors<'a>(
+        &self,
+        v: &mut Vec<(&'a T, i32)>,
+        root: &'a BinarySearchTree<T>,
+    ) -> i32 {
+        match root {
+            BinarySearchTree::Node { value, left, right } => {
+                let left_height = self.recursive_balance_factors(v, left);
+                let index = v.len();
+                v.push((value, 0));
+                let right_height = self.recursive_balance_factors(v, right);
+                v[index].1 = left_height - right_height;
+                1 + left_height.max(right_height)
+            }
+            BinarySearchTree::Empty => 0,
+        }
+    }
+
+    pub fn build_cost(values: &[T]) -> usize
+    where
+        T: Clone,
+    {
+        let mut tree = Self::new();
+        values.iter().map(|value| tree.insert_counting(value.clone())).sum()
+    }
+
+    pub fn iter_bfs(&self) -> impl Iterator<Item = (&T, usize)> {
+        let mut queue = VecDeque::new();
+        queue.push_back((self, 0));
+
+        std::iter::from_fn(move || {
+            while let Some((node, level)) = queue.pop_front() {
+                if let BinarySearchTree::Node { value, left, right } = node {
+                    queue.push_back((left, level + 1));
+                    queue.push_back((right, level + 1));
+                    return Some((value, level));
+                }
+            }
+
+            None
+        })
+    }
+
+    /// Removes and returns the minimum value. Only the leftmost spine is walked, and the minimum
+    /// node is replaced by its right subtree, so the rest of the tree is left untouched.
+    pub fn detach_min(&mut self) -> Option<T> {
+        self.pop_min()
+    }
+
+    pub fn contains_subsequence(&self, pattern: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut remaining = pattern.iter().peekable();
+
+        for value in self.iter() {
+            match remaining.peek() {
+                Some(&expected) if expected == value => {
+                    remaining.next();
+                }
+                Some(_) => {}
+                None => break,
+            }
+        }
+
+        remaining.peek().is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bst_pre_order_traversal_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(Some(vec![&60, &12, &4, &1, &37, &90, &84, &100]), bst.pre_order_traversal(),);
+    }
+
+    #[test]
+    fn bst_in_order_traversal_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(Some(vec![&1, &4, &12, &37, &60, &84, &90, &100]), bst.in_order_traversal(),);
+    }
+
+    #[test]
+    fn bst_post_order_traversal_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(Some(vec![&1, &4, &37, &12, &84, &100, &90, &60]), bst.post_order_traversal(),);
+    }
+
+    #[test]
+    fn bst_breadth_first_traversal_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(
+            Some(vec![&60, &12, &90, &4, &37, &84, &100, &1]),
+            bst.breadth_first_traversal(),
+        );
+    }
+
+    #[test]
+    fn bst_get_by_test() {
+        #[derive(Debug, PartialEq, PartialOrd)]
+        struct Record {
+            id: u32,
+            name: &'static str,
+        }
+
+        let mut bst = BinarySearchTree::new();
+        bst.insert(Record { id: 60, name: "sixty" });
+        bst.insert(Record { id: 12, name: "twelve" });
+        bst.insert(Record { id: 90, name: "ninety" });
+        bst.insert(Record { id: 37, name: "thirty-seven" });
+        assert_eq!(Some("thirty-seven"), bst.get_by(&37, |record| &record.id).map(|r| r.name));
+        assert_eq!(None, bst.get_by(&38, |record| &record.id));
+    }
+
+    #[test]
+    fn bst_shrink_to_fit_test() {
+        let mut bst = BinarySearchTree::new();
+
+        for value in [60, 12, 90, 4, 1, 100, 37, 84] {
+            bst.insert(value);
+        }
+
+        for value in [60, 12, 4, 100, 84] {
+            assert!(bst.remove(&value));
+        }
+
+        assert!(!bst.remove(&60));
+        bst.shrink_to_fit();
+        assert_eq!(Some(vec![&1, &37, &90]), bst.in_order_traversal());
+        assert_eq!(3, bst.len());
+    }
+
+    #[test]
+    fn bst_kth_smallest_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(Some(&1), bst.kth_smallest(0));
+        assert_eq!(Some(&60), bst.kth_smallest(4));
+        assert_eq!(Some(&100), bst.kth_smallest(7));
+        assert_eq!(None, bst.kth_smallest(8));
+    }
+
+    #[test]
+    fn bst_percentile_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        assert_eq!(Some(&37), bst.percentile(0.5));
+        assert_eq!(Some(&1), bst.percentile(0.0));
+        assert_eq!(Some(&100), bst.percentile(1.0));
+        assert_eq!(None, bst.percentile(1.5));
+        assert_eq!(None, bst.percentile(-0.1));
+
+        bst.insert(84);
+        assert_eq!(Some(&37), bst.percentile(0.5));
+        assert_eq!(Some(&84), bst.percentile(0.75));
+    }
+
+    #[test]
+    fn bst_median_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(None, bst.median());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        assert_eq!(Some(MedianResult::One(&37)), bst.median());
+        bst.insert(84);
+        assert_eq!(Some(MedianResult::Two(&37, &60)), bst.median());
+    }
+
+    #[test]
+    fn bst_new_descending_test() {
+        let mut bst = BinarySearchTree::new_descending();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        assert_eq!(Some(vec![&90, &60, &12, &4]), bst.in_order_traversal());
+        bst.insert(100);
+        bst.insert(37);
+        assert_eq!(Some(vec![&100, &90, &60, &37, &12, &4]), bst.in_order_traversal());
+        assert!(bst.contains(&37));
+        assert!(!bst.contains(&38));
+        assert_eq!(SortOrder::Descending, bst.order());
+    }
+
+    #[test]
+    fn bst_partition_point_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(Some(&60), bst.partition_point(|x| *x >= 50));
+        assert_eq!(Some(&37), bst.partition_point(|x| *x >= 13));
+        assert_eq!(Some(&1), bst.partition_point(|_| true));
+        assert_eq!(None, bst.partition_point(|x| *x > 100));
+    }
+
+    #[test]
+    fn bst_values_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(Vec::<&i32>::new(), bst.values());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        assert_eq!(vec![&4, &12, &60, &90], bst.values());
+    }
+
+    #[test]
+    fn bst_vec_traversals_test() {
+        let mut bst = BinarySearchTree::new();
+        assert!(bst.pre_order().is_empty());
+        assert!(bst.post_order().is_empty());
+        assert!(bst.breadth_first().is_empty());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        assert_eq!(vec![&60, &12, &4, &90], bst.pre_order());
+        assert_eq!(vec![&4, &12, &90, &60], bst.post_order());
+        assert_eq!(vec![&60, &12, &90, &4], bst.breadth_first());
+    }
+
+    #[test]
+    fn bst_is_full_and_is_complete_test() {
+        let bst: BinarySearchTree<i32> = BinarySearchTree::new();
+        assert!(bst.is_full());
+        assert!(bst.is_complete());
+
+        let mut full = BinarySearchTree::new();
+        full.insert(60);
+        full.insert(12);
+        full.insert(90);
+        full.insert(84);
+        full.insert(100);
+        assert!(full.is_full());
+        assert!(!full.is_complete());
+
+        let mut complete = BinarySearchTree::new();
+        complete.insert(60);
+        complete.insert(12);
+        complete.insert(90);
+        complete.insert(4);
+        assert!(!complete.is_full());
+        assert!(complete.is_complete());
+    }
+
+    #[test]
+    fn bst_to_dot_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(84);
+        assert_eq!(
+            "digraph BinarySearchTree {\n    n0 [label=\"60\"];\n    n0 -> n1;\n    n1 [label=\"12\"];\n    n0 -> n2;\n    n2 [label=\"90\"];\n    n2 -> n3;\n    n3 [label=\"84\"];\n    n4 [shape=point];\n    n2 -> n4;\n}\n",
+            bst.to_dot(),
+        );
+    }
+
+    #[test]
+    fn bst_successor_and_predecessor_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(37);
+        assert_eq!(Some(&4), bst.min());
+        assert_eq!(Some(&90), bst.max());
+        assert_eq!(Some(&60), bst.successor(&37));
+        assert_eq!(Some(&37), bst.successor(&13));
+        assert_eq!(None, bst.successor(&90));
+        assert_eq!(Some(&12), bst.predecessor(&37));
+        assert_eq!(Some(&37), bst.predecessor(&59));
+        assert_eq!(None, bst.predecessor(&4));
+    }
+
+    #[test]
+    fn bst_traversal_into_test() {
+        let mut first = BinarySearchTree::new();
+        first.insert(60);
+        first.insert(12);
+        first.insert(90);
+        let mut second = BinarySearchTree::new();
+        second.insert(4);
+        second.insert(1);
+
+        let mut buf = Vec::new();
+        first.in_order_into(&mut buf);
+        assert_eq!(vec![&12, &60, &90], buf);
+        second.in_order_into(&mut buf);
+        assert_eq!(vec![&1, &4], buf);
+        first.pre_order_into(&mut buf);
+        assert_eq!(vec![&60, &12, &90], buf);
+        first.post_order_into(&mut buf);
+        assert_eq!(vec![&12, &90, &60], buf);
+    }
+
+    #[test]
+    fn bst_try_in_order_test() {
+        let mut bst = BinarySearchTree::new();
+
+        for value in 0..10 {
+            bst.insert(value);
+        }
+
+        assert_eq!(Err(DepthExceeded { depth: 6 }), bst.try_in_order(5));
+        assert_eq!(bst.values(), bst.try_in_order(10).unwrap());
+    }
+
+    #[test]
+    fn bst_into_sorted_vec_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        assert_eq!(vec![4, 12, 60, 90], bst.into_sorted_vec());
+    }
+
+    #[test]
+    fn bst_split_off_at_rank_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        let sorted = bst.values().into_iter().copied().collect::<Vec<_>>();
+
+        let rest = bst.split_off_at_rank(3);
+        assert_eq!(3, bst.len());
+        assert_eq!(5, rest.len());
+        assert_eq!(Some(vec![&4, &1, &12]), bst.pre_order_traversal());
+        assert_eq!(Some(vec![&84, &60, &37, &100, &90]), rest.pre_order_traversal());
+        assert_eq!(
+            sorted,
+            bst.into_sorted_vec().into_iter().chain(rest.into_sorted_vec()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn bst_append_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        let mut other = BinarySearchTree::new();
+        other.insert(37);
+        other.insert(12);
+        other.insert(100);
+        bst.append(&mut other);
+        assert_eq!(vec![&12, &37, &60, &90, &100], bst.values());
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    fn bst_to_sorted_vec_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        assert_eq!(vec![4, 12, 60, 90], bst.to_sorted_vec());
+        assert_eq!(Some(vec![&60, &12, &4, &90]), bst.pre_order_traversal());
+    }
+
+    #[test]
+    fn bst_nearest_k_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(vec![&60, &37, &84], bst.nearest_k(&50, 3));
+        assert_eq!(vec![&100, &90], bst.nearest_k(&101, 2));
+        assert_eq!(8, bst.nearest_k(&0, 20).len());
+        assert!(bst.nearest_k(&50, 0).is_empty());
+    }
+
+    #[test]
+    fn bst_count_half_nodes_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(0, bst.count_half_nodes());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(1, bst.count_half_nodes());
+        bst.insert(110);
+        bst.insert(120);
+        assert_eq!(3, bst.count_half_nodes());
+    }
+
+    #[test]
+    fn bst_level_sums_test() {
+        let mut bst = BinarySearchTree::new();
+        assert!(bst.level_sums().is_empty());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(vec![60, 102, 225, 1], bst.level_sums());
+    }
+
+    #[test]
+    fn bst_average_node_depth_test() {
+        let mut balanced = BinarySearchTree::new();
+        assert_eq!(0.0, balanced.average_node_depth());
+        balanced.insert(4);
+        balanced.insert(2);
+        balanced.insert(6);
+        balanced.insert(1);
+        balanced.insert(3);
+        balanced.insert(5);
+        balanced.insert(7);
+        let mut degenerate = BinarySearchTree::new();
+
+        for value in 1..=7 {
+            degenerate.insert(value);
+        }
+
+        assert_eq!(10.0 / 7.0, balanced.average_node_depth());
+        assert_eq!(3.0, degenerate.average_node_depth());
+    }
+
+    #[test]
+    fn bst_is_valid_bst_test() {
+        let mut bst = BinarySearchTree::new();
+        assert!(bst.is_valid_bst());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(37);
+        assert!(bst.is_valid_bst());
+
+        let invalid = BinarySearchTree::Node {
+            value: 60,
+            left: Box::new(BinarySearchTree::Node {
+                value: 12,
+                left: Box::new(BinarySearchTree::Empty),
+                right: Box::new(BinarySearchTree::Node {
+                    value: 70,
+                    left: Box::new(BinarySearchTree::Empty),
+                    right: Box::new(BinarySearchTree::Empty),
+                }),
+            }),
+            right: Box::new(BinarySearchTree::Empty),
+        };
+        assert!(!invalid.is_valid_bst());
+    }
+
+    #[test]
+    fn bst_verify_and_repair_test() {
+        let mut bst = BinarySearchTree::Node {
+            value: 60,
+            left: Box::new(BinarySearchTree::Node {
+                value: 90,
+                left: Box::new(BinarySearchTree::Empty),
+                right: Box::new(BinarySearchTree::Empty),
+            }),
+            right: Box::new(BinarySearchTree::Node {
+                value: 12,
+                left: Box::new(BinarySearchTree::Empty),
+                right: Box::new(BinarySearchTree::Empty),
+            }),
+        };
+        assert!(bst.verify_and_repair());
+        assert!(bst.is_valid_bst());
+        assert_eq!(Some(vec![&60, &12, &90]), bst.pre_order_traversal());
+        assert!(!bst.verify_and_repair());
+    }
+
+    #[test]
+    fn bst_dedup_test() {
+        let mut bst = BinarySearchTree::Node {
+            value: 60,
+            left: Box::new(BinarySearchTree::Node {
+                value: 12,
+                left: Box::new(BinarySearchTree::Empty),
+                right: Box::new(BinarySearchTree::Node {
+                    value: 60,
+                    left: Box::new(BinarySearchTree::Empty),
+                    right: Box::new(BinarySearchTree::Empty),
+                }),
+            }),
+            right: Box::new(BinarySearchTree::Node {
+                value: 90,
+                left: Box::new(BinarySearchTree::Node {
+                    value: 90,
+                    left: Box::new(BinarySearchTree::Empty),
+                    right: Box::new(BinarySearchTree::Empty),
+                }),
+                right: Box::new(BinarySearchTree::Empty),
+            }),
+        };
+        assert_eq!(5, bst.len());
+        bst.dedup();
+        assert_eq!(3, bst.len());
+        assert_eq!(vec![&12, &60, &90], bst.values());
+
+        bst.dedup();
+        assert_eq!(Some(vec![&60, &12, &90]), bst.pre_order_traversal());
+    }
+
+    #[test]
+    fn bst_diff_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        let mut other = BinarySearchTree::new();
+        other.insert(37);
+        other.insert(12);
+        other.insert(100);
+        other.insert(90);
+        assert_eq!((vec![&4, &60], vec![&37, &100]), bst.diff(&other));
+        assert_eq!((vec![], vec![]), bst.diff(&bst));
+    }
+
+    #[test]
+    fn bst_insert_if_absent_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        assert_eq!(Ok(&90), bst.insert_if_absent(90));
+        assert_eq!(Err(&12), bst.insert_if_absent(12));
+        assert_eq!(vec![&12, &60, &90], bst.values());
+    }
+
+    #[test]
+    fn bst_rotate_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(37);
+        bst.insert(84);
+        bst.insert(100);
+        let sorted = bst.values().into_iter().copied().collect::<Vec<_>>();
+
+        assert!(bst.rotate_left());
+        assert_eq!(Some(vec![&90, &60, &12, &4, &37, &84, &100]), bst.pre_order_traversal());
+        assert_eq!(sorted, bst.values().into_iter().copied().collect::<Vec<_>>());
+
+        assert!(bst.rotate_right());
+        assert!(bst.rotate_right());
+        assert_eq!(Some(vec![&12, &4, &60, &37, &90, &84, &100]), bst.pre_order_traversal());
+        assert_eq!(sorted, bst.values().into_iter().copied().collect::<Vec<_>>());
+        assert!(bst.is_valid_bst());
+
+        let mut leaf = BinarySearchTree::new();
+        leaf.insert(1);
+        assert!(!leaf.rotate_left());
+        assert!(!leaf.rotate_right());
+        assert_eq!(Some(vec![&1]), leaf.pre_order_traversal());
+    }
+
+    #[test]
+    fn bst_contains_counting_test() {
+        let mut bst = BinarySearchTree::new();
+
+        for value in 0..10 {
+            bst.insert(value);
+        }
+
+        assert_eq!((true, 1), bst.contains_counting(&0));
+        assert_eq!((true, 8), bst.contains_counting(&7));
+        assert_eq!((false, 10), bst.contains_counting(&10));
+        assert!(bst.contains(&9));
+        assert!(!bst.contains(&-1));
+    }
+
+    #[test]
+    fn bst_modify_or_insert_test() {
+        #[derive(Debug)]
+        struct Counted {
+            key: u32,
+            hits: u32,
+        }
+
+        impl PartialEq for Counted {
+            fn eq(&self, other: &Self) -> bool {
+                self.key == other.key
+            }
+        }
+
+        impl PartialOrd for Counted {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.key.partial_cmp(&other.key)
+            }
+        }
+
+        let mut bst = BinarySearchTree::new();
+        bst.insert(Counted { key: 60, hits: 1 });
+        bst.insert(Counted { key: 12, hits: 1 });
+        bst.modify_or_insert(Counted { key: 60, hits: 1 }, |counted| counted.hits += 1);
+        bst.modify_or_insert(Counted { key: 90, hits: 1 }, |counted| counted.hits += 1);
+        assert_eq!(
+            vec![(12, 1), (60, 2), (90, 1)],
+            bst.values().into_iter().map(|counted| (counted.key, counted.hits)).collect::<Vec<_>>(),
+        );
+
+        let mut floats = BinarySearchTree::new();
+        floats.insert(1.0);
+        floats.insert(2.0);
+        floats.modify_or_insert(f64::NAN, |value| *value = 100.0);
+        assert_eq!(vec![&1.0, &2.0], floats.values());
+        assert!(floats.is_valid_bst());
+    }
+
+    #[test]
+    fn bst_truncate_to_test() {
+        let mut bst = BinarySearchTree::new();
+
+        for value in 0..100 {
+            bst.insert((value * 37) % 100);
+        }
+
+        bst.truncate_to(10);
+        assert_eq!(10, bst.len());
+        assert_eq!((0..10).collect::<Vec<_>>(), bst.into_sorted_vec());
+
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.truncate_to(5);
+        assert_eq!(Some(vec![&60, &12]), bst.pre_order_traversal());
+        bst.truncate_to(0);
+        assert!(bst.is_empty());
+    }
+
+    #[test]
+    fn bst_has_unique_values_test() {
+        let mut bst = BinarySearchTree::new();
+        assert!(bst.has_unique_values());
+        bst.insert(60);
+        assert!(bst.has_unique_values());
+        bst.insert(12);
+        bst.insert(90);
+        assert!(bst.has_unique_values());
+
+        let duplicated = BinarySearchTree::Node {
+            value: 60,
+            left: Box::new(BinarySearchTree::Node {
+                value: 12,
+                left: Box::new(BinarySearchTree::Empty),
+                right: Box::new(BinarySearchTree::Node {
+                    value: 60,
+                    left: Box::new(BinarySearchTree::Empty),
+                    right: Box::new(BinarySearchTree::Empty),
+                }),
+            }),
+            right: Box::new(BinarySearchTree::Empty),
+        };
+        assert!(!duplicated.has_unique_values());
+    }
+
+    #[test]
+    fn bst_fold_order_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(37);
+        let concat = |mut acc: String, value: &i32| {
+            acc.push_str(&value.to_string());
+            acc.push(' ');
+            acc
+        };
+        assert_eq!("4 37 12 90 60 ", bst.fold_order(DfsOrder::PostOrder, String::new(), concat));
+        assert_eq!("60 12 4 37 90 ", bst.fold_order(DfsOrder::PreOrder, String::new(), concat));
+        assert_eq!(203, bst.fold_order(DfsOrder::InOrder, 0, |acc, value| acc + value));
+    }
+
+    #[test]
+    fn bst_has_path_sum_test() {
+        let mut bst = BinarySearchTree::new();
+        assert!(!bst.has_path_sum(0));
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert!(bst.has_path_sum(109));
+        assert!(bst.has_path_sum(77));
+        assert!(!bst.has_path_sum(72));
+        assert!(!bst.has_path_sum(150));
+    }
+
+    #[test]
+    fn bst_min_gap_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(None, bst.min_gap());
+        bst.insert(60);
+        assert_eq!(None, bst.min_gap());
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(Some(3), bst.min_gap());
+    }
+
+    #[test]
+    fn bst_insert_traced_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!((vec![], true), bst.insert_traced(60));
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        assert_eq!((vec![Direction::Left, Direction::Right], true), bst.insert_traced(37));
+        assert_eq!(
+            (vec![Direction::Left, Direction::Left, Direction::Right], true),
+            bst.insert_traced(5),
+        );
+        assert_eq!((vec![Direction::Right], false), bst.insert_traced(90));
+        assert_eq!(Some(vec![&60, &12, &4, &5, &37, &90]), bst.pre_order_traversal());
+
+        let mut floats = BinarySearchTree::new();
+        floats.insert(1.0);
+        floats.insert(2.0);
+        assert_eq!((vec![], false), floats.insert_traced(f64::NAN));
+        assert_eq!(2, floats.len());
+    }
+
+    #[test]
+    fn bst_count_less_and_count_greater_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(3, bst.count_less(&37));
+        assert_eq!(4, bst.count_greater(&37));
+        assert_eq!(4, bst.count_less(&50));
+        assert_eq!(4, bst.count_greater(&50));
+
+        for target in [0, 1, 37, 50, 100, 101] {
+            let present = usize::from(bst.contains(&target));
+            assert_eq!(bst.len(), bst.count_less(&target) + bst.count_greater(&target) + present);
+        }
+    }
+
+    #[test]
+    fn bst_height_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(0, bst.height());
+        bst.insert(60);
+        assert_eq!(1, bst.height());
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        assert_eq!(4, bst.height());
+    }
+
+    #[test]
+    fn bst_rebuild_randomized_test() {
+        let mut bst = BinarySearchTree::new();
+
+        for value in 0..200 {
+            bst.insert(value);
+        }
+
+        assert_eq!(200, bst.height());
+        bst.rebuild_randomized(42);
+        assert!(bst.height() < 40);
+        assert!(bst.is_valid_bst());
+        assert_eq!((0..200).collect::<Vec<_>>(), bst.into_sorted_vec());
+    }
+
+    #[test]
+    fn bst_pop_min_and_pop_max_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(None, bst.pop_min());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(37);
+        assert_eq!(Some(4), bst.pop_min());
+        assert_eq!(Some(12), bst.pop_min());
+        assert_eq!(Some(90), bst.pop_max());
+        assert_eq!(vec![&37, &60], bst.values());
+    }
+
+    #[test]
+    fn bst_subtree_at_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+
+        let subtree = bst.subtree_at(&12).unwrap();
+        assert_eq!(Some(&1), subtree.min());
+        assert_eq!(Some(&37), subtree.max());
+        assert_eq!(Some(vec![&12, &4, &1, &37]), subtree.pre_order_traversal());
+        assert!(bst.subtree_at(&13).is_none());
+    }
+
+    #[test]
+    fn bst_depth_histogram_test() {
+        let mut bst = BinarySearchTree::new();
+        assert!(bst.depth_histogram().is_empty());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(vec![1, 2, 4, 1], bst.depth_histogram());
+        assert_eq!(bst.len(), bst.depth_histogram().iter().sum::<usize>());
+    }
+
+    #[test]
+    fn bst_prune_leaves_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(4, bst.prune_leaves());
+        assert_eq!(Some(vec![&60, &12, &4, &90]), bst.pre_order_traversal());
+        assert!(bst.is_valid_bst());
+        assert_eq!(2, bst.prune_leaves());
+        assert_eq!(1, bst.prune_leaves());
+        assert_eq!(1, bst.prune_leaves());
+        assert!(bst.is_empty());
+        assert_eq!(0, bst.prune_leaves());
+    }
+
+    #[test]
+    fn bst_map_in_place_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(37);
+        bst.map_in_place(|value| *value += 10);
+        assert!(bst.is_valid_bst());
+        assert_eq!(vec![&14, &22, &47, &70, &100], bst.values());
+        assert_eq!(Some(vec![&70, &22, &14, &47, &100]), bst.pre_order_traversal());
+    }
+
+    #[test]
+    fn bst_try_insert_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(Err(IncomparableError), bst.try_insert(f64::NAN));
+        assert!(bst.is_empty());
+        assert_eq!(Ok(true), bst.try_insert(60.0));
+        assert_eq!(Ok(true), bst.try_insert(12.5));
+        assert_eq!(Ok(false), bst.try_insert(60.0));
+        assert_eq!(Err(IncomparableError), bst.try_insert(f64::NAN));
+        assert_eq!(vec![&12.5, &60.0], bst.values());
+    }
+
+    #[test]
+    fn bst_filter_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(vec![&4, &12, &60, &84, &90, &100], bst.filter(|value| value % 2 == 0));
+        assert!(bst.filter(|value| *value > 100).is_empty());
+    }
+
+    #[test]
+    fn bst_content_fingerprint_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        let mut other = BinarySearchTree::new();
+        other.insert(4);
+        other.insert(12);
+        other.insert(60);
+        other.insert(90);
+        assert_ne!(bst.pre_order(), other.pre_order());
+        assert_eq!(bst.content_fingerprint(), other.content_fingerprint());
+        other.insert(37);
+        assert_ne!(bst.content_fingerprint(), other.content_fingerprint());
+    }
+
+    #[test]
+    fn bst_contains_sorted_batch_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        let queries = [0, 1, 1, 5, 37, 60, 61, 100, 101];
+        assert_eq!(
+            queries.iter().map(|query| bst.contains(query)).collect::<Vec<_>>(),
+            bst.contains_sorted_batch(&queries),
+        );
+        assert_eq!(
+            vec![false, true, true, false, true, true, false, true, false],
+            bst.contains_sorted_batch(&queries)
+        );
+    }
+
+    #[test]
+    fn bst_node_test() {
+        let bst = BinarySearchTree::node(
+            60,
+            BinarySearchTree::node(12, BinarySearchTree::Empty, BinarySearchTree::Empty),
+            BinarySearchTree::node(90, BinarySearchTree::Empty, BinarySearchTree::Empty),
+        );
+        assert_eq!(Some(vec![&60, &12, &90]), bst.pre_order_traversal());
+        assert_eq!(Some(vec![&12, &60, &90]), bst.in_order_traversal());
+        assert_eq!(Some(vec![&12, &90, &60]), bst.post_order_traversal());
+        assert!(bst.is_valid_bst());
+    }
+
+    #[test]
+    fn bst_is_perfect_test() {
+        let mut bst = BinarySearchTree::new();
+        assert!(bst.is_perfect());
+        assert_eq!(0, bst.perfect_prefix_levels());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(37);
+        bst.insert(84);
+        bst.insert(100);
+        assert!(bst.is_perfect());
+        assert_eq!(3, bst.perfect_prefix_levels());
+        bst.insert(1);
+        assert!(!bst.is_perfect());
+        assert_eq!(3, bst.perfect_prefix_levels());
+        bst.remove(&84);
+        assert_eq!(2, bst.perfect_prefix_levels());
+    }
+
+    #[test]
+    fn bst_insert_balanced_batch_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert_balanced_batch(vec![60, 12, 90, 4, 12]);
+        assert_eq!(Some(vec![&60, &12, &4, &90]), bst.pre_order_traversal());
+
+        for batch in 0..10 {
+            bst.insert_balanced_batch((0..100).map(|value| value * 10 + batch).rev().collect());
+            assert!(bst.is_valid_bst());
+            assert!(bst.is_perfect() || bst.perfect_prefix_levels() + 1 == bst.height());
+        }
+
+        assert_eq!(1000, bst.len());
+        assert_eq!(10, bst.height());
+    }
+
+    #[test]
+    fn bst_to_level_order_array_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(Some(vec![]), bst.to_level_order_array(0));
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(37);
+        bst.insert(84);
+        bst.insert(40);
+        assert_eq!(
+            Some(vec![
+                Some(&60),
+                Some(&12),
+                Some(&90),
+                None,
+                Some(&37),
+                Some(&84),
+                None,
+                None,
+                None,
+                None,
+                Some(&40)
+            ]),
+            bst.to_level_order_array(11),
+        );
+        assert_eq!(None, bst.to_level_order_array(10));
+
+        let mut degenerate = BinarySearchTree::new();
+
+        for value in 0..100 {
+            degenerate.insert(value);
+        }
+
+        assert_eq!(None, degenerate.to_level_order_array(1 << 20));
+        assert_eq!(None, degenerate.to_level_order_array(usize::MAX));
+
+        let mut left_chain = BinarySearchTree::new();
+
+        for value in (0..63).rev() {
+            left_chain.insert(value);
+        }
+
+        assert_eq!(None, left_chain.to_level_order_array(1 << 20));
+        assert_eq!(None, left_chain.to_level_order_array(usize::MAX));
+        left_chain.insert(-1);
+        assert_eq!(None, left_chain.to_level_order_array(usize::MAX));
+    }
+
+    #[test]
+    fn bst_from_level_order_array_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(37);
+        bst.insert(84);
+        bst.insert(40);
+        let array = bst
+            .to_level_order_array(usize::MAX)
+            .unwrap()
+            .into_iter()
+            .map(|value| value.copied())
+            .collect::<Vec<_>>();
+        let rebuilt = BinarySearchTree::from_level_order_array(&array);
+        assert_eq!(bst.pre_order(), rebuilt.pre_order());
+        assert_eq!(bst.to_level_order_array(usize::MAX), rebuilt.to_level_order_array(usize::MAX));
+
+        let unordered = BinarySearchTree::from_level_order_array(&[Some(1), Some(2), Some(3)]);
+        assert_eq!(Some(vec![&1, &2, &3]), unordered.pre_order_traversal());
+        assert!(!unordered.is_valid_bst());
+    }
+
+    #[test]
+    fn bst_count_occurrences_in_range_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(37);
+        assert_eq!(3, bst.count_occurrences_in_range(&12, &60));
+        assert_eq!(0, bst.count_occurrences_in_range(&13, &36));
+
+        let multiset = BinarySearchTree::node(
+            37,
+            BinarySearchTree::node(
+                12,
+                BinarySearchTree::Empty,
+                BinarySearchTree::node(37, BinarySearchTree::Empty, BinarySearchTree::Empty),
+            ),
+            BinarySearchTree::node(
+                60,
+                BinarySearchTree::node(37, BinarySearchTree::Empty, BinarySearchTree::Empty),
+                BinarySearchTree::node(90, BinarySearchTree::Empty, BinarySearchTree::Empty),
+            ),
+        );
+        assert_eq!(4, multiset.count_occurrences_in_range(&30, &60));
+        assert_eq!(3, multiset.count_occurrences_in_range(&37, &37));
+    }
+
+    #[test]
+    fn bst_remove_subtree_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+
+        let removed = bst.remove_subtree(&4).unwrap();
+        assert_eq!(Some(vec![&4, &1]), removed.pre_order_traversal());
+        assert!(removed.is_valid_bst());
+        assert_eq!(Some(vec![&60, &12, &37, &90, &84, &100]), bst.pre_order_traversal());
+        assert!(bst.remove_subtree(&4).is_none());
+
+        let removed = bst.remove_subtree(&60).unwrap();
+        assert_eq!(6, removed.len());
+        assert!(bst.is_empty());
+    }
+
+    #[test]
+    fn bst_graft_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(100);
+
+        let mut chunk = BinarySearchTree::new();
+        chunk.insert(37);
+        chunk.insert(20);
+        chunk.insert(50);
+        assert!(bst.graft(chunk).is_ok());
+        assert_eq!(Some(vec![&60, &12, &4, &37, &20, &50, &90, &100]), bst.pre_order_traversal());
+        assert!(bst.is_valid_bst());
+
+        let mut overlapping = BinarySearchTree::new();
+        overlapping.insert(80);
+        overlapping.insert(95);
+        let rejected = bst.graft(overlapping).unwrap_err();
+        assert_eq!(Some(vec![&80, &95]), rejected.pre_order_traversal());
+        assert_eq!(8, bst.len());
+    }
+
+    #[test]
+    fn bst_ordering_violations_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        assert!(bst.ordering_violations().is_empty());
+
+        let invalid = BinarySearchTree::node(
+            60,
+            BinarySearchTree::node(
+                12,
+                BinarySearchTree::node(15, BinarySearchTree::Empty, BinarySearchTree::Empty),
+                BinarySearchTree::node(70, BinarySearchTree::Empty, BinarySearchTree::Empty),
+            ),
+            BinarySearchTree::node(90, BinarySearchTree::Empty, BinarySearchTree::Empty),
+        );
+        assert_eq!(vec![(&12, &15), (&60, &70)], invalid.ordering_violations());
+    }
+
+    #[test]
+    fn bst_most_frequent_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(None, bst.most_frequent());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        assert_eq!(Some((&12, 1)), bst.most_frequent());
+
+        let multiset = BinarySearchTree::node(
+            37,
+            BinarySearchTree::node(
+                12,
+                BinarySearchTree::node(12, BinarySearchTree::Empty, BinarySearchTree::Empty),
+                BinarySearchTree::node(37, BinarySearchTree::Empty, BinarySearchTree::Empty),
+            ),
+            BinarySearchTree::node(
+                60,
+                BinarySearchTree::node(37, BinarySearchTree::Empty, BinarySearchTree::Empty),
+                BinarySearchTree::node(90, BinarySearchTree::Empty, BinarySearchTree::Empty),
+            ),
+        );
+        assert_eq!(Some((&37, 3)), multiset.most_frequent());
+    }
+
+    #[test]
+    fn bst_leaves_test() {
+        let mut bst = BinarySearchTree::new();
+        assert!(bst.leaves().is_empty());
+        assert_eq!(None, bst.iter_leaves().next());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(vec![&1, &37, &84, &100], bst.leaves());
+        assert_eq!(bst.leaves(), bst.iter_leaves().collect::<Vec<_>>());
+        assert_eq!(Some(&84), bst.iter_leaves().find(|value| **value > 50));
+        assert_eq!(4, bst.iter_leaves().count());
+    }
+
+    #[test]
+    fn bst_same_shape_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(37);
+        let mut other = BinarySearchTree::new();
+        other.insert("m");
+        other.insert("c");
+        other.insert("x");
+        other.insert("d");
+        assert!(bst.same_shape(&other));
+        other.insert("a");
+        assert!(!bst.same_shape(&other));
+        assert!(BinarySearchTree::<i32>::new().same_shape(&BinarySearchTree::<f64>::new()));
+    }
+
+    #[test]
+    fn bst_count_paths_with_sum_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(0, bst.count_paths_with_sum(0));
+        bst.insert(10);
+        bst.insert(5);
+        bst.insert(15);
+        bst.insert(3);
+        bst.insert(7);
+        bst.insert(12);
+        bst.insert(18);
+        bst.insert(2);
+        assert_eq!(2, bst.count_paths_with_sum(15));
+        assert_eq!(2, bst.count_paths_with_sum(12));
+        assert_eq!(1, bst.count_paths_with_sum(20));
+        assert_eq!(0, bst.count_paths_with_sum(100));
+    }
+
+    #[test]
+    fn bst_ranked_values_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        assert_eq!(vec![(0, &4), (1, &12), (2, &60), (3, &90)], bst.ranked_values());
+
+        for (rank, value) in bst.ranked_values() {
+            assert_eq!(Some(value), bst.kth_smallest(rank));
+        }
+    }
+
+    #[test]
+    fn bst_merge_balanced_test() {
+        let mut a = BinarySearchTree::new();
+        a.insert(60);
+        a.insert(12);
+        a.insert(90);
+        let mut b = BinarySearchTree::new();
+        b.insert(37);
+        b.insert(12);
+        b.insert(100);
+        b.insert(4);
+        let merged = BinarySearchTree::merge_balanced(a, b);
+        assert_eq!(vec![&4, &12, &37, &60, &90, &100], merged.values());
+        assert_eq!(3, merged.height());
+
+        let mut evens = BinarySearchTree::new();
+        let mut odds = BinarySearchTree::new();
+        evens.insert_balanced_batch((0..50_000).map(|value| value * 2).collect());
+        odds.insert_balanced_batch((0..50_000).map(|value| value * 2 + 1).collect());
+        let merged = BinarySearchTree::merge_balanced(evens, odds);
+        assert_eq!(100_000, merged.len());
+        assert_eq!(17, merged.height());
+        assert_eq!((0..100_000).collect::<Vec<_>>(), merged.into_sorted_vec());
+    }
+
+    #[test]
+    fn bst_in_order_small_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(SmallValues::Empty, bst.in_order_small());
+        assert!(bst.in_order_small().is_empty());
+        bst.insert(60);
+        assert_eq!(SmallValues::One([&60]), bst.in_order_small());
+        assert_eq!(&[&60], &*bst.in_order_small());
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        assert_eq!(bst.values(), bst.in_order_small().to_vec());
+    }
+
+    #[test]
+    fn bst_is_symmetric_test() {
+        let mut bst = BinarySearchTree::new();
+        assert!(bst.is_symmetric());
+        bst.insert(60);
+        assert!(bst.is_symmetric());
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(100);
+        assert!(bst.is_symmetric());
+        bst.insert(37);
+        assert!(!bst.is_symmetric());
+        bst.insert(84);
+        assert!(bst.is_symmetric());
+        bst.insert(1);
+        assert!(!bst.is_symmetric());
+    }
+
+    #[test]
+    fn bst_partition_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(
+            (vec![&4, &12, &60, &84, &90, &100], vec![&1, &37]),
+            bst.partition(|value| value % 2 == 0),
+        );
+    }
+
+    #[test]
+    fn bst_has_close_pair_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        assert!(!bst.has_close_pair(100));
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert!(bst.has_close_pair(3));
+        assert!(bst.has_close_pair(4));
+        assert!(!bst.has_close_pair(2));
+    }
+
+    #[test]
+    fn bst_sample_test() {
+        let mut bst = BinarySearchTree::new();
+
+        for value in 0..100 {
+            bst.insert((value * 37) % 100);
+        }
+
+        let sample = bst.sample(5, 7);
+        assert_eq!(5, sample.len());
+        assert_eq!(sample, bst.sample(5, 7));
+        assert!(sample.iter().all(|value| bst.contains(value)));
+        assert_ne!(sample, bst.sample(5, 8));
+        assert_eq!(100, bst.sample(200, 7).len());
+        assert!(bst.sample(0, 7).is_empty());
+    }
+
+    #[test]
+    fn bst_peek_min_and_peek_max_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(None, bst.peek_min());
+        assert_eq!(None, bst.peek_max());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        assert_eq!(bst.min(), bst.peek_min());
+        assert_eq!(bst.max(), bst.peek_max());
+        assert_eq!(Some(&4), bst.peek_min());
+        bst.pop_min();
+        assert_eq!(Some(&12), bst.peek_min());
+    }
+
+    #[test]
+    fn bst_distinct_len_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(0, bst.distinct_len());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        assert_eq!(bst.len(), bst.distinct_len());
+
+        let multiset = BinarySearchTree::node(
+            37,
+            BinarySearchTree::node(
+                12,
+                BinarySearchTree::node(12, BinarySearchTree::Empty, BinarySearchTree::Empty),
+                BinarySearchTree::node(37, BinarySearchTree::Empty, BinarySearchTree::Empty),
+            ),
+            BinarySearchTree::node(60, BinarySearchTree::Empty, BinarySearchTree::Empty),
+        );
+        assert_eq!(5, multiset.len());
+        assert_eq!(3, multiset.distinct_len());
+    }
+
+    #[test]
+    fn bst_from_sorted_iter_test() {
+        let bst = BinarySearchTree::from_sorted_iter(0..1000);
+        assert_eq!(1000, bst.len());
+        assert_eq!(10, bst.height());
+        assert!(bst.is_valid_bst());
+        assert_eq!((0..1000).collect::<Vec<_>>(), bst.into_sorted_vec());
+        assert!(BinarySearchTree::from_sorted_iter(0..0).is_empty());
+
+        let lines = "apple\nbanana\n\ncherry\ndate\n".lines().filter(|line| !line.is_empty());
+        let bst = BinarySearchTree::from_sorted_iter(lines);
+        assert_eq!(vec![&"apple", &"banana", &"cherry", &"date"], bst.values());
+        assert_eq!(3, bst.height());
+
+        let bst = BinarySearchTree::from_sorted_iter((0..).map_while(|n| (n < 100).then_some(n)));
+        assert_eq!(100, bst.len());
+        assert_eq!(7, bst.height());
+
+        struct Misreported<I>(I, usize);
+
+        impl<I: Iterator> Iterator for Misreported<I> {
+            type Item = I::Item;
+
+            fn next(&mut self) -> Option<I::Item> {
+                self.0.next()
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.1, Some(self.1))
+            }
+        }
+
+        for hint in [0, 2, 10, 50] {
+            let bst = BinarySearchTree::from_sorted_iter(Misreported(0..10, hint));
+            assert_eq!((0..10).collect::<Vec<_>>(), bst.into_sorted_vec());
+        }
+
+        let bst = BinarySearchTree::from_sorted_iter(Misreported(0..1000, 2));
+        assert_eq!(1000, bst.len());
+        assert_eq!(10, bst.height());
+    }
+
+    #[test]
+    fn bst_take_test() {
+        #[derive(Debug)]
+        struct Job {
+            priority: u32,
+            name: String,
+        }
+
+        impl PartialEq for Job {
+            fn eq(&self, other: &Self) -> bool {
+                self.priority == other.priority
+            }
+        }
+
+        impl PartialOrd for Job {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.priority.partial_cmp(&other.priority)
+            }
+        }
+
+        let mut bst = BinarySearchTree::new();
+        bst.insert(Job { priority: 60, name: String::from("backup") });
+        bst.insert(Job { priority: 12, name: String::from("cleanup") });
+        bst.insert(Job { priority: 90, name: String::from("deploy") });
+
+        let probe = Job { priority: 60, name: String::new() };
+        assert_eq!(Some(String::from("backup")), bst.take(&probe).map(|job| job.name));
+        assert!(!bst.contains(&probe));
+        assert!(bst.take(&probe).is_none());
+        assert_eq!(2, bst.len());
+    }
+
+    #[test]
+    fn bst_splay_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        let sorted = bst.to_sorted_vec();
+
+        for target in [1, 37, 84, 100, 60, 4] {
+            assert!(bst.splay(&target));
+            assert_eq!(Some(&target), bst.pre_order().first().copied());
+            assert!(bst.is_valid_bst());
+            assert_eq!(sorted, bst.to_sorted_vec());
+        }
+
+        assert!(!bst.splay(&50));
+        assert!(bst.is_valid_bst());
+        assert_eq!(sorted, bst.to_sorted_vec());
+        assert!(!BinarySearchTree::new().splay(&1));
+    }
+
+    #[test]
+    fn bst_internal_path_length_test() {
+        let mut balanced = BinarySearchTree::new();
+        assert_eq!(0, balanced.internal_path_length());
+        balanced.insert(60);
+        balanced.insert(12);
+        balanced.insert(90);
+        balanced.insert(4);
+        balanced.insert(1);
+        balanced.insert(100);
+        balanced.insert(37);
+        balanced.insert(84);
+        assert_eq!(13, balanced.internal_path_length());
+
+        let mut degenerate = BinarySearchTree::new();
+
+        for value in 0..8 {
+            degenerate.insert(value);
+        }
+
+        assert_eq!(28, degenerate.internal_path_length());
+        assert!(balanced.internal_path_length() < degenerate.internal_path_length());
+    }
+
+    #[test]
+    fn bst_group_by_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        bst.insert(20);
+        bst.insert(5);
+        let groups = bst.group_by(|value| value % 3);
+        assert_eq!(3, groups.len());
+        assert_eq!(vec![&12, &60, &84, &90], groups[&0]);
+        assert_eq!(vec![&1, &4, &37, &100], groups[&1]);
+        assert_eq!(vec![&5, &20], groups[&2]);
+
+        let groups = bst.group_by(|value| value % 2 == 0);
+        assert_eq!(vec![&1, &5, &37], groups[&false]);
+    }
+
+    #[test]
+    fn bst_satisfies_min_heap_test() {
+        let mut bst = BinarySearchTree::new();
+        assert!(bst.satisfies_min_heap());
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        assert!(!bst.satisfies_min_heap());
+
+        let heap = BinarySearchTree::node(
+            1,
+            BinarySearchTree::node(
+                4,
+                BinarySearchTree::node(37, BinarySearchTree::Empty, BinarySearchTree::Empty),
+                BinarySearchTree::node(12, BinarySearchTree::Empty, BinarySearchTree::Empty),
+            ),
+            BinarySearchTree::node(60, BinarySearchTree::Empty, BinarySearchTree::Empty),
+        );
+        assert!(heap.satisfies_min_heap());
+        assert!(!heap.is_valid_bst());
+    }
+
+    #[test]
+    fn bst_ancestors_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(Some(vec![&4, &12, &60]), bst.ancestors(&1));
+        assert_eq!(Some(vec![&90, &60]), bst.ancestors(&84));
+        assert_eq!(Some(vec![]), bst.ancestors(&60));
+        assert_eq!(None, bst.ancestors(&2));
+    }
+
+    #[test]
+    fn bst_subtree_size_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(Some(bst.len()), bst.subtree_size(&60));
+        assert_eq!(Some(4), bst.subtree_size(&12));
+        assert_eq!(Some(1), bst.subtree_size(&37));
+        assert_eq!(None, bst.subtree_size(&38));
+    }
+
+    #[test]
+    fn bst_find_first_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(Some(&60), bst.find_first(|value| *value > 50));
+        assert_eq!(Some(&1), bst.find_first(|value| value % 2 == 1));
+        assert_eq!(None, bst.find_first(|value| *value > 100));
+
+        let calls = std::cell::Cell::new(0);
+        assert_eq!(
+            Some(&12),
+            bst.find_first(|value| {
+                calls.set(calls.get() + 1);
+                *value > 10
+            })
+        );
+        assert_eq!(3, calls.get());
+    }
+
+    #[test]
+    fn bst_to_newick_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(";", bst.to_newick());
+        bst.insert(60);
+        assert_eq!("60;", bst.to_newick());
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(1);
+        bst.insert(37);
+        bst.insert(84);
+        bst.insert(100);
+        assert_eq!("((1,37)12,(84,100)90)60;", bst.to_newick());
+        bst.insert(0);
+        bst.insert(110);
+        assert_eq!("(((0,)1,37)12,(84,(,110)100)90)60;", bst.to_newick());
+
+        let mut labels = BinarySearchTree::new();
+        labels.insert(String::from("m"));
+        labels.insert(String::from("a,b"));
+        labels.insert(String::from("it's"));
+        labels.insert(String::from(""));
+        assert_eq!("(('','it''s')'a,b',)m;", labels.to_newick());
+    }
+
+    #[test]
+    fn bst_from_newick_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(1);
+        bst.insert(37);
+        bst.insert(84);
+        bst.insert(100);
+        bst.insert(0);
+        bst.insert(110);
+
+        let parsed = BinarySearchTree::<i32>::from_newick(&bst.to_newick()).unwrap();
+        assert_eq!(bst.pre_order(), parsed.pre_order());
+        assert!(bst.same_shape(&parsed));
+        assert!(BinarySearchTree::<i32>::from_newick("").unwrap().is_empty());
+        assert!(BinarySearchTree::<i32>::from_newick(";").unwrap().is_empty());
+
+        for newick in ["(1,2)3;", "(1, 2)3", " ( 1 ,\n2 ) 3 ; ", "(1,2)'3';"] {
+            let parsed = BinarySearchTree::<i32>::from_newick(newick).unwrap();
+            assert_eq!(vec![&3, &1, &2], parsed.pre_order());
+        }
+
+        let mut labels = BinarySearchTree::new();
+
+        for label in ["m", "a,b", "(x)", "it's", "semi;colon", "two words", "", "z"] {
+            labels.insert(String::from(label));
+        }
+
+        let parsed = BinarySearchTree::<String>::from_newick(&labels.to_newick()).unwrap();
+        assert_eq!(labels.pre_order(), parsed.pre_order());
+        assert!(labels.same_shape(&parsed));
+
+        let quoted = BinarySearchTree::<String>::from_newick("('a,b','it''s (x)')'m n';").unwrap();
+        assert_eq!(vec!["m n", "a,b", "it's (x)"], quoted.pre_order());
+        assert_eq!(vec![""], BinarySearchTree::<String>::from_newick("''").unwrap().pre_order());
+    }
+
+    #[test]
+    fn bst_from_newick_error_test() {
+        assert_eq!(
+            Err(ParseError::UnexpectedEnd),
+            BinarySearchTree::<i32>::from_newick("(1,2").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::UnexpectedChar { position: 2, found: ')' }),
+            BinarySearchTree::<i32>::from_newick("(1)2").map(|_| ()),
+        );
+        assert_eq!(
+            Err(ParseError::MissingLabel { position: 5 }),
+            BinarySearchTree::<i32>::from_newick("(1,2)").map(|_| ()),
+        );
+        assert_eq!(
+            Err(ParseError::InvalidLabel { position: 3, label: String::from("x") }),
+            BinarySearchTree::<i32>::from_newick("(1,x)2").map(|_| ()),
+        );
+        assert_eq!(
+            Err(ParseError::UnexpectedChar { position: 1, found: ')' }),
+            BinarySearchTree::<i32>::from_newick("1)").map(|_| ()),
+        );
+        assert_eq!(
+            Err(ParseError::UnexpectedChar { position: 7, found: '2' }),
+            BinarySearchTree::<i32>::from_newick("(1,2)3;2").map(|_| ()),
+        );
+        assert_eq!(
+            Err(ParseError::UnexpectedEnd),
+            BinarySearchTree::<String>::from_newick("(a,'b)c").map(|_| ()),
+        );
+
+        let nested = |depth: usize| {
+            let mut newick = "(".repeat(depth) + "0";
+            (1..=depth).for_each(|label| newick.push_str(&format!(",){}", label)));
+            newick
+        };
+
+        let parsed = BinarySearchTree::<usize>::from_newick(&nested(MAX_NEWICK_DEPTH)).unwrap();
+        assert_eq!(MAX_NEWICK_DEPTH + 1, parsed.height());
+        assert_eq!(
+            Err(ParseError::TooDeep { position: MAX_NEWICK_DEPTH }),
+            BinarySearchTree::<usize>::from_newick(&nested(MAX_NEWICK_DEPTH + 1)).map(|_| ()),
+        );
+        assert_eq!(
+            Err(ParseError::TooDeep { position: MAX_NEWICK_DEPTH }),
+            BinarySearchTree::<i32>::from_newick(&"(".repeat(1_000_000)).map(|_| ()),
+        );
+    }
+
+    #[test]
+    fn bst_distance_to_nearest_leaf_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(37);
+        bst.insert(100);
+        bst.insert(110);
+        assert_eq!(Some(0), bst.distance_to_nearest_leaf(&1));
+        assert_eq!(Some(1), bst.distance_to_nearest_leaf(&12));
+        assert_eq!(Some(1), bst.distance_to_nearest_leaf(&4));
+        assert_eq!(Some(2), bst.distance_to_nearest_leaf(&90));
+        assert_eq!(Some(2), bst.distance_to_nearest_leaf(&60));
+        assert_eq!(None, bst.distance_to_nearest_leaf(&2));
+    }
+
+    #[test]
+    fn bst_batch_rank_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        let targets = [0, 1, 2, 37, 37, 50, 100, 101];
+        assert_eq!(vec![0, 0, 1, 3, 3, 4, 7, 8], bst.batch_rank(&targets));
+        assert_eq!(
+            targets.iter().map(|target| bst.count_less(target)).collect::<Vec<_>>(),
+            bst.batch_rank(&targets),
+        );
+    }
+
+    #[test]
+    fn bst_rebalance_toward_test() {
+        let mut bst = BinarySearchTree::new();
+
+        for value in 0..100 {
+            bst.insert(value);
+        }
+
+        bst.rebalance_toward(BalancePolicy::MinHeight);
+        assert_eq!(7, bst.height());
+        assert!(bst.is_valid_bst());
+        assert_eq!((0..100).collect::<Vec<_>>(), bst.to_sorted_vec());
+
+        bst.rebalance_toward(BalancePolicy::Random(42));
+        assert!(bst.is_valid_bst());
+        assert_eq!((0..100).collect::<Vec<_>>(), bst.to_sorted_vec());
+    }
+
+    #[test]
+    fn bst_range_mut_test() {
+        #[derive(Debug)]
+        struct Price {
+            id: u32,
+            cents: u32,
+        }
+
+        impl PartialEq for Price {
+            fn eq(&self, other: &Self) -> bool {
+                self.id == other.id
+            }
+        }
+
+        impl PartialOrd for Price {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.id.partial_cmp(&other.id)
+            }
+        }
+
+        let mut bst = BinarySearchTree::new();
+
+        for id in [60, 12, 90, 4, 1, 100, 37, 84] {
+            bst.insert(Price { id, cents: 100 });
+        }
+
+        let low = Price { id: 12, cents: 0 };
+        let high = Price { id: 84, cents: 0 };
+
+        for price in bst.range_mut(&low, &high) {
+            price.cents += 50;
+        }
+
+        assert_eq!(
+            vec![
+                (1, 100),
+                (4, 100),
+                (12, 150),
+                (37, 150),
+                (60, 150),
+                (84, 150),
+                (90, 100),
+                (100, 100)
+            ],
+            bst.values().into_iter().map(|price| (price.id, price.cents)).collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn bst_skew_test() {
+        let mut bst = BinarySearchTree::new();
+        assert_eq!(Skew::Balanced, bst.skew());
+
+        for value in 0..10 {
+            bst.insert(value);
+        }
+
+        assert_eq!(Skew::RightHeavy, bst.skew());
+
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        assert_eq!(Skew::Balanced, bst.skew());
+        bst.insert(4);
+        assert_eq!(Skew::LeftHeavy, bst.skew());
+    }
+
+    #[test]
+    fn bst_remove_range_test() {
+        let mut bst = BinarySearchTree::new();
+        bst.insert(60);
+        bst.insert(12);
+        bst.insert(90);
+        bst.insert(4);
+        bst.insert(1);
+        bst.insert(100);
+        bst.insert(37);
+        bst.insert(84);
+        assert_eq!(4, bst.remove_range(&10, &85));
+        assert_eq!(vec![&1, &4, &90, &100], bst.values());
+        assert!(bst.is_valid_bst());
+        assert_eq!(0, bst.remove_range(&5, &89));
+        assert_eq!(4, bst.remove_range(&0, &100));
+        assert!(bst.is_empty());
+    }
+
+    #[test]
+    fn bst_into_balanced_test() {
+        #[derive(Debug)]
+        struct Name(String);
+
+        impl PartialEq for Name {
+            fn eq(&self, other: &Self) -> bool {
+                self.0.len() == other.0.len() && self.0 == other.0
+            }
+        }
+
+        impl PartialOrd for Name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.0.len().cmp(&other.0.len()).then_with(|| self.0.cmp(&other.0)))
+            }
+        }
+
+        let mut bst = BinarySearchTree::new();
+
+        for len in 1..=15 {
+            bst.insert(Name("x".repeat(len)));
+        }
+
+        assert_eq!(15, bst.height());
+        let bst = bst.into_balanced();
+        assert_eq!(4, bst.height());
+        assert!(bst.is_valid_bst());
+        assert_eq!(
+            (1..=15).collect::<Vec<_>>(),
+            bst.values().into_iter().map(|name| name.0.len()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn bst_range_counting_test() {
+        let mut bst = BinarySearchTree::from_sorted_iter(0..1023);
+        let (values, visited) = bst.range_counting(&500, &503);
+        assert_eq!(vec![&500, &501, &502, &503], values);
+        assert!(visited < 30);
+        assert!(visited < bst.len());
+
+        bst = BinarySearchTree::new();
+
+        for value in [60, 12, 90, 4, 1, 100, 37, 84] {
+            bst.insert(value);
+        }
+
+        assert_eq!((vec![&37, &60, &84], 5), bst.range_counting(&20, &85));
+        assert_eq!((vec![], 0), BinarySearchTree::new().range_counting(&1, &2));
+    }
+
+    #[test]
+    fn bst_merge_iter_test() {
+        let mut a = BinarySearchTree::new();
+        let mut b = BinarySearchTree::new();
+
+        for value in [60, 12, 90, 4, 1, 100, 37, 84] {
+            a.insert(value);
+        }
+
+        for value in [37, 5, 90, 61, 2, 100] {
+            b.insert(value);
+        }
+
+        assert_eq!(
+            vec![&1, &2, &4, &5, &12, &37, &60, &61, &84, &90, &100],
+            a.merge_iter(&b).collect::<Vec<_>>()
+        );
+        assert_eq!(a.values(), a.merge_iter(&BinarySearchTree::new()).collect::<Vec<_>>());
+        assert_eq!(vec![&1, &2], a.merge_iter(&b).take(2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn bst_index_of_test() {
+        let mut bst = BinarySearchTree::new();
+
+        for value in [60, 12, 90, 4, 1, 100, 37, 84] {
+            bst.insert(value);
+        }
+
+        assert_eq!(Some(0), bst.index_of(&1));
+        assert_eq!(Some(4), bst.index_of(&60));
+        assert_eq!(Some(7), bst.index_of(&100));
+
+        for (index, value) in bst.iter().enumerate() {
+            assert_eq!(Some(index), bst.index_of(value));
+            assert_eq!(Some(value), bst.kth_smallest(index));
+        }
+
+        assert_eq!(None, bst.index_of(&50));
+        assert_eq!(None, BinarySearchTree::new().index_of(&1));
+    }
+
+    #[test]
+    fn bst_balance_factors_test() {
+        let mut bst = BinarySearchTree::new();
+
+        for value in [60, 12, 90, 4, 1, 100, 37, 84] {
+            bst.insert(value);
+        }
+
+        assert_eq!(
+            vec![(&1, 0), (&4, 1), (&12, 1), (&37, 0), (&60, 1), (&84, 0), (&90, 0), (&100, 0)],
+            bst.balance_factors()
+        );
+
+        bst.insert(110);
+        bst.insert(120);
+        assert_eq!(Some(&(&90, -2)), bst.balance_factors().iter().find(|(value, _)| **value == 90));
+        assert!(BinarySearchTree::<i32>::new().balance_factors().is_empty());
+    }
+
+    #[test]
+    fn bst_build_cost_test() {
+        assert_eq!(21, BinarySearchTree::build_cost(&[1, 2, 3, 4, 5, 6, 7]));
+        assert_eq!(10, BinarySearchTree::build_cost(&[4, 2, 6, 1, 3, 5, 7]));
+        assert_eq!(0, BinarySearchTree::<i32>::build_cost(&[]));
+    }
+
+    #[test]
+    fn bst_iter_bfs_test() {
+        let mut bst = BinarySearchTree::new();
+
+        for value in [60, 12, 90, 4, 1, 100, 37, 84] {
+            bst.insert(value);
+        }
+
+        assert_eq!(
+            vec![(&60, 0), (&12, 1), (&90, 1), (&4, 2), (&37, 2), (&84, 2), (&100, 2), (&1, 3)],
+            bst.iter_bfs().collect::<Vec<_>>()
+        );
+        assert_eq!(bst.breadth_first(), bst.iter_bfs().map(|(value, _)| value).collect::<Vec<_>>());
+        assert_eq!(vec![(&60, 0), (&12, 1)], bst.iter_bfs().take(2).collect::<Vec<_>>());
+        assert_eq!(Some((&1, 3)), bst.iter_bfs().find(|&(_, level)| level == 3));
+        assert_eq!(None, BinarySearchTree::<i32>::new().iter_bfs().next());
+    }
+
+    #[test]
+    fn bst_detach_min_test() {
+        let mut bst = BinarySearchTree::new();
+
+        for value in [60, 12, 90, 4, 1, 100, 37, 84] {
+            bst.insert(value);
+        }
+
+        let mut detached = Vec::new();
+
+        while let Some(value) = bst.detach_min() {
+            detached.push(value);
+            assert!(bst.is_valid_bst());
+        }
+
+        assert_eq!(vec![1, 4, 12, 37, 60, 84, 90, 100], detached);
+        assert!(bst.is_empty());
+    }
+
+    #[test]
+    fn bst_contains_subsequence_test() {
+        let mut bst = BinarySearchTree::new();
+
+        for value in [60, 12, 90, 4, 1, 100, 37, 84] {
+            bst.insert(value);
+        }
+
+        assert!(bst.contains_subsequence(&[4, 37, 84, 100]));
+        assert!(bst.contains_subsequence(&[]));
+        assert!(!bst.contains_subsequence(&[37, 12]));
+        assert!(!bst.contains_subsequence(&[4, 50]));
     }
 }
@@ -0,0 +1,226 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::{BinarySearchTree, IncomparableError};
+
+#[derive(Debug)]
+struct KeyValue<K, V> {
+    key: K,
+    value: V,
+}
+
+impl<K, V> PartialEq for KeyValue<K, V>
+where
+    K: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K, V> PartialOrd for KeyValue<K, V>
+where
+    K: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.key.partial_cmp(&other.key)
+    }
+}
+
+pub struct BinarySearchMap<K, V>
+where
+    K: fmt::Debug + PartialOrd,
+    V: fmt::Debug,
+{
+    tree: BinarySearchTree<KeyValue<K, V>>,
+}
+
+impl<K, V> Default for BinarySearchMap<K, V>
+where
+    K: fmt::Debug + PartialOrd,
+    V: fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> BinarySearchMap<K, V>
+where
+    K: fmt::Debug + PartialOrd,
+    V: fmt::Debug,
+{
+    pub fn new() -> Self {
+        BinarySearchMap { tree: BinarySearchTree::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut node = &self.tree;
+
+        while let BinarySearchTree::Node { value: pair, left, right } = node {
+            match key.partial_cmp(&pair.key) {
+                Some(Ordering::Less) => node = left,
+                Some(Ordering::Greater) => node = right,
+                Some(Ordering::Equal) => return Some(&pair.value),
+                None => return None,
+            }
+        }
+
+        None
+    }
+
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V>
+    where
+        K: Ord,
+    {
+        let mut node = &mut self.tree;
+
+        loop {
+            match node {
+                BinarySearchTree::Node { value: pair, left, right } => match key.cmp(&pair.key) {
+                    Ordering::Less => node = left,
+                    Ordering::Greater => node = right,
+                    Ordering::Equal => return Entry::Occupied(OccupiedEntry { pair }),
+                },
+                BinarySearchTree::Empty => return Entry::Vacant(VacantEntry { key, slot: node }),
+            }
+        }
+    }
+
+    /// Like [`entry`](Self::entry) for keys that are only `PartialOrd`. A key that cannot be
+    /// compared, such as NaN, is rejected instead of being matched with another key's slot.
+    pub fn try_entry(&mut self, key: K) -> Result<Entry<'_, K, V>, IncomparableError> {
+        let mut node = &mut self.tree;
+
+        loop {
+            match node {
+                BinarySearchTree::Node { value: pair, left, right } => {
+                    match key.partial_cmp(&pair.key) {
+                        Some(Ordering::Less) => node = left,
+                        Some(Ordering::Greater) => node = right,
+                        Some(Ordering::Equal) => {
+                            return Ok(Entry::Occupied(OccupiedEntry { pair }))
+                        }
+                        None => return Err(IncomparableError),
+                    }
+                }
+                BinarySearchTree::Empty => {
+                    if key.partial_cmp(&key).is_none() {
+                        return Err(IncomparableError);
+                    }
+
+                    return Ok(Entry::Vacant(VacantEntry { key, slot: node }));
+                }
+            }
+        }
+    }
+}
+
+pub enum Entry<'a, K, V>
+where
+    K: fmt::Debug + PartialOrd,
+    V: fmt::Debug,
+{
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+pub struct OccupiedEntry<'a, K, V> {
+    pair: &'a mut KeyValue<K, V>,
+}
+
+pub struct VacantEntry<'a, K, V>
+where
+    K: fmt::Debug + PartialOrd,
+    V: fmt::Debug,
+{
+    key: K,
+    slot: &'a mut BinarySearchTree<KeyValue<K, V>>,
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: fmt::Debug + PartialOrd,
+    V: fmt::Debug,
+{
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => &entry.pair.key,
+            Entry::Vacant(entry) => &entry.key,
+        }
+    }
+
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(ref mut entry) = self {
+            f(&mut entry.pair.value);
+        }
+
+        self
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => &mut entry.pair.value,
+            Entry::Vacant(entry) => {
+                *entry.slot = BinarySearchTree::Node {
+                    value: KeyValue { key: entry.key, value: default() },
+                    left: Box::new(BinarySearchTree::Empty),
+                    right: Box::new(BinarySearchTree::Empty),
+                };
+
+                match entry.slot {
+                    BinarySearchTree::Node { value: pair, .. } => &mut pair.value,
+                    BinarySearchTree::Empty => unreachable!(),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bsm_entry_word_count_test() {
+        let mut map = BinarySearchMap::new();
+
+        for word in "the quick brown fox jumps over the lazy dog the fox".split_whitespace() {
+            map.entry(word).and_modify(|count| *count += 1).or_insert(1);
+        }
+
+        assert_eq!(8, map.len());
+        assert_eq!(Some(&3), map.get(&"the"));
+        assert_eq!(Some(&2), map.get(&"fox"));
+        assert_eq!(Some(&1), map.get(&"dog"));
+        assert_eq!(None, map.get(&"cat"));
+    }
+
+    #[test]
+    fn bsm_entry_incomparable_test() {
+        let mut map = BinarySearchMap::new();
+        map.try_entry(1.0).unwrap().or_insert("one");
+        map.try_entry(2.0).unwrap().and_modify(|value| *value = "none").or_insert("two");
+
+        assert_eq!(None, map.get(&f64::NAN));
+        assert!(matches!(map.try_entry(f64::NAN), Err(IncomparableError)));
+        assert!(matches!(
+            BinarySearchMap::<f64, &str>::new().try_entry(f64::NAN),
+            Err(IncomparableError)
+        ));
+        assert_eq!(2, map.len());
+        assert_eq!(Some(&"one"), map.get(&1.0));
+        assert_eq!(Some(&"two"), map.get(&2.0));
+    }
+}
@@ -0,0 +1,105 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::BinarySearchTree;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    fn compare<T: PartialOrd>(self, a: &T, b: &T) -> Option<Ordering> {
+        match self {
+            SortOrder::Ascending => a.partial_cmp(b),
+            SortOrder::Descending => b.partial_cmp(a),
+        }
+    }
+}
+
+/// A tree that keeps its values in the given [`SortOrder`]. With [`SortOrder::Descending`] the
+/// larger values are placed on the left, so an in-order walk yields them from largest to smallest.
+pub struct OrderedBst<T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    tree: BinarySearchTree<T>,
+    order: SortOrder,
+}
+
+impl<T> OrderedBst<T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    pub fn new(order: SortOrder) -> Self {
+        OrderedBst { tree: BinarySearchTree::new(), order }
+    }
+
+    pub fn order(&self) -> SortOrder {
+        self.order
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn insert(&mut self, new_value: T) {
+        let mut node = &mut self.tree;
+
+        while let BinarySearchTree::Node { value, left, right } = node {
+            match self.order.compare(&new_value, value) {
+                Some(Ordering::Less) => node = left,
+                Some(Ordering::Greater) => node = right,
+                _ => return,
+            }
+        }
+
+        node.insert(new_value);
+    }
+
+    pub fn contains(&self, target: &T) -> bool {
+        let mut node = &self.tree;
+
+        while let BinarySearchTree::Node { value, left, right } = node {
+            match self.order.compare(target, value) {
+                Some(Ordering::Less) => node = left,
+                Some(Ordering::Greater) => node = right,
+                Some(Ordering::Equal) => return true,
+                None => return false,
+            }
+        }
+
+        false
+    }
+
+    pub fn values(&self) -> Vec<&T> {
+        self.tree.values()
+    }
+
+    pub fn in_order_traversal(&self) -> Option<Vec<&T>> {
+        self.tree.in_order_traversal()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordered_bst_ascending_test() {
+        let mut ordered = OrderedBst::new(SortOrder::Ascending);
+
+        for value in [60, 12, 90, 4, 1, 100, 37, 84] {
+            ordered.insert(value);
+        }
+
+        assert_eq!(vec![&1, &4, &12, &37, &60, &84, &90, &100], ordered.values());
+        assert!(ordered.contains(&37));
+        assert!(!ordered.contains(&38));
+        assert_eq!(8, ordered.len());
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+
+use crate::BinarySearchTree;
+
+impl<T> BinarySearchTree<T>
+where
+    T: fmt::Debug + PartialOrd + Sync,
+{
+    pub fn par_fold<B, F, R>(&self, identity: B, fold: F, reduce: R) -> B
+    where
+        B: Clone + Send + Sync,
+        F: Fn(B, &T) -> B + Send + Sync,
+        R: Fn(B, B) -> B + Send + Sync,
+    {
+        self.recursive_par_fold(self, &identity, &fold, &reduce)
+    }
+
+    fn recursive_par_fold<B, F, R>(
+        &self,
+        root: &BinarySearchTree<T>,
+        identity: &B,
+        fold: &F,
+        reduce: &R,
+    ) -> B
+    where
+        B: Clone + Send + Sync,
+        F: Fn(B, &T) -> B + Send + Sync,
+        R: Fn(B, B) -> B + Send + Sync,
+    {
+        match root {
+            BinarySearchTree::Node { value, left, right } => {
+                let (left, right) = rayon::join(
+                    || self.recursive_par_fold(left, identity, fold, reduce),
+                    || self.recursive_par_fold(right, identity, fold, reduce),
+                );
+                reduce(fold(left, value), right)
+            }
+            BinarySearchTree::Empty => identity.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bst_par_fold_test() {
+        let mut bst = BinarySearchTree::new();
+
+        for value in 0..10_000u64 {
+            bst.insert((value * 7919) % 10_000);
+        }
+
+        let sequential = bst.values().into_iter().sum::<u64>();
+        assert_eq!(sequential, bst.par_fold(0, |acc, value| acc + value, |a, b| a + b));
+        assert_eq!(
+            bst.to_sorted_vec(),
+            bst.par_fold(
+                Vec::new(),
+                |mut acc, value| {
+                    acc.push(*value);
+                    acc
+                },
+                |mut a, b| {
+                    a.extend(b);
+                    a
+                }
+            ),
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use crate::BinarySearchTree;
+
+pub struct ProfiledBst<T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    tree: BinarySearchTree<T>,
+    total_comparisons: usize,
+}
+
+impl<T> Default for ProfiledBst<T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ProfiledBst<T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    pub fn new() -> Self {
+        ProfiledBst { tree: BinarySearchTree::new(), total_comparisons: 0 }
+    }
+
+    pub fn insert(&mut self, value: T) {
+        self.total_comparisons += self.tree.insert_counting(value);
+    }
+
+    pub fn total_comparisons(&self) -> usize {
+        self.total_comparisons
+    }
+
+    pub fn as_tree(&self) -> &BinarySearchTree<T> {
+        &self.tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiled_bst_total_comparisons_test() {
+        let mut degenerate = ProfiledBst::new();
+
+        for value in 1..=7 {
+            degenerate.insert(value);
+        }
+
+        let mut balanced = ProfiledBst::new();
+
+        for value in [4, 2, 6, 1, 3, 5, 7] {
+            balanced.insert(value);
+        }
+
+        assert_eq!(21, degenerate.total_comparisons());
+        assert_eq!(10, balanced.total_comparisons());
+        assert_eq!(balanced.as_tree().values(), degenerate.as_tree().values());
+    }
+}
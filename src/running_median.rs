@@ -0,0 +1,161 @@
+use std::fmt;
+
+use crate::{BinarySearchTree, IncomparableError, MedianResult};
+
+pub struct RunningMedian<T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    lower: BinarySearchTree<(T, u64)>,
+    upper: BinarySearchTree<(T, u64)>,
+    lower_len: usize,
+    upper_len: usize,
+    next_seq: u64,
+}
+
+impl<T> Default for RunningMedian<T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> RunningMedian<T>
+where
+    T: fmt::Debug + PartialOrd,
+{
+    pub fn new() -> Self {
+        RunningMedian {
+            lower: BinarySearchTree::new(),
+            upper: BinarySearchTree::new(),
+            lower_len: 0,
+            upper_len: 0,
+            next_seq: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.lower_len + self.upper_len
+    }
+
+    /// Adds a value, keeping both halves balanced so that insertion and the median stay O(log n)
+    /// even for sorted streams. Values that cannot be compared with themselves, such as NaN, are
+    /// rejected.
+    pub fn insert(&mut self, value: T) -> Result<(), IncomparableError> {
+        if value.partial_cmp(&value).is_none() {
+            return Err(IncomparableError);
+        }
+
+        let entry = (value, self.next_seq);
+
+        if self.lower.max().is_none_or(|(max, _)| entry.0 <= *max) {
+            if !self.lower.insert_scapegoat(entry, self.lower_len + 1) {
+                return Err(IncomparableError);
+            }
+
+            self.lower_len += 1;
+        } else {
+            if !self.upper.insert_scapegoat(entry, self.upper_len + 1) {
+                return Err(IncomparableError);
+            }
+
+            self.upper_len += 1;
+        }
+
+        self.next_seq += 1;
+
+        if self.lower_len > self.upper_len + 1 {
+            if let Some(entry) = self.lower.pop_max() {
+                self.lower_len -= 1;
+                self.upper_len += 1;
+                self.upper.insert_scapegoat(entry, self.upper_len);
+            }
+        } else if self.upper_len > self.lower_len {
+            if let Some(entry) = self.upper.pop_min() {
+                self.upper_len -= 1;
+                self.lower_len += 1;
+                self.lower.insert_scapegoat(entry, self.lower_len);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn median(&self) -> Option<MedianResult<&T>> {
+        let (lower, _) = self.lower.max()?;
+
+        if self.lower_len > self.upper_len {
+            Some(MedianResult::One(lower))
+        } else {
+            self.upper.min().map(|(upper, _)| MedianResult::Two(lower, upper))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_median_test() {
+        let mut running = RunningMedian::new();
+        let mut seen = Vec::new();
+        assert_eq!(None, running.median());
+
+        for value in [5, 15, 1, 3, 8, 7, 9, 10, 20, 2, 3, 3, 15, 0] {
+            running.insert(value).unwrap();
+            seen.push(value);
+            seen.sort();
+
+            let mid = seen.len() / 2;
+            let expected = if seen.len() % 2 == 1 {
+                MedianResult::One(&seen[mid])
+            } else {
+                MedianResult::Two(&seen[mid - 1], &seen[mid])
+            };
+            assert_eq!(Some(expected), running.median());
+        }
+
+        assert_eq!(14, running.len());
+    }
+
+    #[test]
+    fn running_median_incomparable_test() {
+        let mut running = RunningMedian::new();
+        assert_eq!(Ok(()), running.insert(1.0));
+        assert_eq!(Ok(()), running.insert(2.0));
+        assert_eq!(Err(IncomparableError), running.insert(f64::NAN));
+        assert_eq!(Ok(()), running.insert(3.0));
+        assert_eq!(3, running.len());
+        assert_eq!(Some(MedianResult::One(&2.0)), running.median());
+    }
+
+    #[test]
+    fn running_median_sorted_stream_test() {
+        let mut running = RunningMedian::new();
+
+        for value in 0..10_000 {
+            running.insert(value).unwrap();
+        }
+
+        assert_eq!(Some(MedianResult::Two(&4999, &5000)), running.median());
+        assert!(running.lower.height() <= 25);
+        assert!(running.upper.height() <= 25);
+        assert!(running.lower.is_valid_bst());
+        assert!(running.upper.is_valid_bst());
+
+        for value in (10_000..20_000).rev() {
+            running.insert(value).unwrap();
+        }
+
+        assert_eq!(Some(MedianResult::Two(&9999, &10000)), running.median());
+        assert!(running.lower.height() <= 25);
+        assert!(running.upper.height() <= 25);
+    }
+}
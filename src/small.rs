@@ -0,0 +1,20 @@
+use std::ops::Deref;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SmallValues<'a, T> {
+    Empty,
+    One([&'a T; 1]),
+    Many(Vec<&'a T>),
+}
+
+impl<'a, T> Deref for SmallValues<'a, T> {
+    type Target = [&'a T];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            SmallValues::Empty => &[],
+            SmallValues::One(value) => value,
+            SmallValues::Many(values) => values,
+        }
+    }
+}